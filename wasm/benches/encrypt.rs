@@ -104,4 +104,71 @@ mod tests {
             });
         });
     }
+
+    fn batch_fixture(n: usize) -> (String, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let mut key = [0u8; 32];
+        rng.fill(&mut key[..]);
+        let key_secret = format!("keySecret_z{}", bs58::encode(key).into_string());
+
+        let nonce_materials: Vec<Vec<u8>> = (0..n)
+            .map(|i| format!("{{\"in\":\"co_zTest\",\"tx\":{{\"sessionID\":\"session_zTest\",\"txIndex\":{i}}}}}").into_bytes())
+            .collect();
+        let ciphertexts = nonce_materials
+            .iter()
+            .map(|nonce_material| {
+                let mut plaintext = [0u8; 64];
+                rng.fill(&mut plaintext[..]);
+                encrypt_internal(&plaintext, &key_secret, nonce_material)
+                    .unwrap()
+                    .into_vec()
+            })
+            .collect();
+        (key_secret, ciphertexts, nonce_materials)
+    }
+
+    #[bench]
+    fn bench_decrypt_one_by_one(b: &mut Bencher) {
+        let (key_secret, ciphertexts, nonce_materials) = batch_fixture(1000);
+        b.iter(|| {
+            for (ciphertext, nonce_material) in ciphertexts.iter().zip(&nonce_materials) {
+                test::black_box(decrypt_internal(ciphertext, &key_secret, nonce_material).unwrap());
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_decrypt_many(b: &mut Bencher) {
+        let (key_secret, ciphertexts, nonce_materials) = batch_fixture(1000);
+        b.iter(|| {
+            let results =
+                decrypt_many_internal(&ciphertexts, &key_secret, &nonce_materials).unwrap();
+            assert!(results.iter().all(|result| result.is_ok()));
+            test::black_box(results);
+        });
+    }
+
+    #[bench]
+    fn bench_encrypt_parsing_secret(b: &mut Bencher) {
+        let key_secret = new_random_key_secret_internal(false).secret();
+        let plaintext = [7u8; 64];
+        b.iter(|| {
+            (0..test::black_box(1000)).for_each(|_| {
+                test::black_box(encrypt_internal(&plaintext, &key_secret, b"nonce").unwrap());
+            });
+        });
+    }
+
+    #[bench]
+    fn bench_encrypt_with_symmetric_key(b: &mut Bencher) {
+        let key_secret = new_random_key_secret_internal(false).secret();
+        let key = SymmetricKey::new_internal(&key_secret).unwrap();
+        let plaintext = [7u8; 64];
+        b.iter(|| {
+            (0..test::black_box(1000)).for_each(|_| {
+                test::black_box(key.encrypt_internal(&plaintext, b"nonce").unwrap());
+            });
+        });
+    }
 }
@@ -0,0 +1,34 @@
+#![feature(test)]
+extern crate test;
+
+#[cfg(test)]
+mod tests {
+    use jazz_crypto_rs::crypto::kdf::*;
+    use test::Bencher;
+
+    const PASSPHRASE: &[u8] = b"correct horse battery staple";
+    const SALT: [u8; 16] = [7u8; 16];
+
+    fn bench_params(b: &mut Bencher, params: KdfParams) {
+        b.iter(|| {
+            test::black_box(
+                derive_secret_seed_from_passphrase_internal(PASSPHRASE, &SALT, &params).unwrap(),
+            )
+        });
+    }
+
+    // Browser-safe defaults: 64 MiB, 3 passes, 1 lane
+    #[bench]
+    fn bench_derive_secret_seed_default(b: &mut Bencher) {
+        bench_params(b, KdfParams::new());
+    }
+
+    // OWASP minimum: 19 MiB, 2 passes, 1 lane
+    #[bench]
+    fn bench_derive_secret_seed_owasp_minimum(b: &mut Bencher) {
+        let mut params = KdfParams::new();
+        params.set_memory_kib(19 * 1024);
+        params.set_iterations(2);
+        bench_params(b, params);
+    }
+}
@@ -0,0 +1,60 @@
+#![feature(test)]
+extern crate test;
+
+// ML-DSA-65 next to Ed25519, to weigh the cost of post-quantum signatures.
+// Sizes: verifying key 1952 bytes (Ed25519: 32), signature 3309 bytes (Ed25519: 64).
+// Run with `cargo bench --features ml-dsa --bench ml_dsa`.
+#[cfg(test)]
+mod tests {
+    use jazz_crypto_rs::crypto::ed25519::*;
+    use jazz_crypto_rs::crypto::ml_dsa::*;
+    use test::Bencher;
+
+    const MESSAGE: &[u8] = b"{\"changes\":[{\"op\":\"set\",\"key\":\"name\",\"value\":\"Jazz\"}]}";
+
+    #[bench]
+    fn bench_ml_dsa_key_generation(b: &mut Bencher) {
+        b.iter(|| {
+            let signing_key = new_ml_dsa_signing_key();
+            test::black_box(ml_dsa_verifying_key(&signing_key).unwrap());
+        });
+    }
+
+    #[bench]
+    fn bench_ml_dsa_sign(b: &mut Bencher) {
+        let signing_key = new_ml_dsa_signing_key();
+        b.iter(|| test::black_box(ml_dsa_sign(&signing_key, MESSAGE).unwrap()));
+    }
+
+    #[bench]
+    fn bench_ml_dsa_verify(b: &mut Bencher) {
+        let signing_key = new_ml_dsa_signing_key();
+        let verifying_key = ml_dsa_verifying_key(&signing_key).unwrap();
+        let signature = ml_dsa_sign(&signing_key, MESSAGE).unwrap();
+        assert_eq!(verifying_key.len(), ML_DSA_VERIFYING_KEY_LENGTH);
+        assert_eq!(signature.len(), ML_DSA_SIGNATURE_LENGTH);
+        b.iter(|| assert!(ml_dsa_verify(&verifying_key, MESSAGE, &signature).unwrap()));
+    }
+
+    #[bench]
+    fn bench_ed25519_key_generation(b: &mut Bencher) {
+        b.iter(|| {
+            let signing_key = new_ed25519_signing_key();
+            test::black_box(ed25519_verifying_key(&signing_key).unwrap());
+        });
+    }
+
+    #[bench]
+    fn bench_ed25519_sign(b: &mut Bencher) {
+        let signing_key = new_ed25519_signing_key();
+        b.iter(|| test::black_box(ed25519_sign(&signing_key, MESSAGE).unwrap()));
+    }
+
+    #[bench]
+    fn bench_ed25519_verify(b: &mut Bencher) {
+        let signing_key = new_ed25519_signing_key();
+        let verifying_key = ed25519_verifying_key(&signing_key).unwrap();
+        let signature = ed25519_sign(&signing_key, MESSAGE).unwrap();
+        b.iter(|| assert!(ed25519_verify(&verifying_key, MESSAGE, &signature).unwrap()));
+    }
+}
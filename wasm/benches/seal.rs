@@ -145,4 +145,48 @@ mod tests {
             });
         });
     }
+
+    #[bench]
+    fn bench_seal_parsing_secret(b: &mut Bencher) {
+        let sender_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(new_x25519_private_key()).into_string()
+        );
+        let recipient_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(new_x25519_private_key()).into_string()
+        );
+        let recipient_id = get_sealer_id_internal(&recipient_secret).unwrap();
+        let message = [7u8; 64];
+        b.iter(|| {
+            (0..test::black_box(1000)).for_each(|_| {
+                test::black_box(
+                    seal_internal(&message, &sender_secret, &recipient_id, b"nonce").unwrap(),
+                );
+            });
+        });
+    }
+
+    #[bench]
+    fn bench_seal_with_sealer_key(b: &mut Bencher) {
+        let sender_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(new_x25519_private_key()).into_string()
+        );
+        let recipient_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(new_x25519_private_key()).into_string()
+        );
+        let recipient_id = get_sealer_id_internal(&recipient_secret).unwrap();
+        let key = SealerKey::new_internal(&sender_secret).unwrap();
+        let message = [7u8; 64];
+        b.iter(|| {
+            (0..test::black_box(1000)).for_each(|_| {
+                test::black_box(
+                    key.seal_internal(&message, &recipient_id, b"nonce")
+                        .unwrap(),
+                );
+            });
+        });
+    }
 }
@@ -23,7 +23,7 @@ mod tests {
             let secret = format!("signerSecret_z{}", bs58::encode(&signing_key).into_string());
 
             // Sign the message
-            let signature = sign(message, &secret.as_bytes())?;
+            let signature = sign(message, secret.as_bytes())?;
 
             // Get the public key for verification
             let secret_bytes =
@@ -33,11 +33,7 @@ mod tests {
             let signer_id = format!("signer_z{}", bs58::encode(&verifying_key).into_string());
 
             // Verify the signature
-            assert!(verify(
-                &signature.as_bytes(),
-                message,
-                &signer_id.as_bytes()
-            )?);
+            assert!(verify(signature.as_bytes(), message, signer_id.as_bytes())?);
             Ok(())
         };
         b.iter(|| {
@@ -140,11 +136,11 @@ mod tests {
             let secret = format!("signerSecret_z{}", bs58::encode(&signing_key).into_string());
 
             // Get signer ID
-            let signer_id = get_signer_id(&secret.as_bytes())?;
+            let signer_id = get_signer_id(secret.as_bytes())?;
             assert!(signer_id.starts_with("signer_z"));
 
             // Test that same secret produces same ID
-            let signer_id2 = get_signer_id(&secret.as_bytes())?;
+            let signer_id2 = get_signer_id(secret.as_bytes())?;
             assert_eq!(signer_id, signer_id2);
 
             // Test invalid secret format
@@ -191,4 +187,29 @@ mod tests {
             });
         });
     }
+
+    #[bench]
+    fn bench_sign_parsing_secret(b: &mut Bencher) {
+        let signing_key = new_ed25519_signing_key();
+        let secret = format!("signerSecret_z{}", bs58::encode(&signing_key).into_string());
+        let message = [7u8; 64];
+        b.iter(|| {
+            (0..test::black_box(1000)).for_each(|_| {
+                test::black_box(sign(&message, secret.as_bytes()).unwrap());
+            });
+        });
+    }
+
+    #[bench]
+    fn bench_sign_with_signer_key(b: &mut Bencher) {
+        let signing_key = new_ed25519_signing_key();
+        let secret = format!("signerSecret_z{}", bs58::encode(&signing_key).into_string());
+        let key = SignerKey::new_internal(&secret).unwrap();
+        let message = [7u8; 64];
+        b.iter(|| {
+            (0..test::black_box(1000)).for_each(|_| {
+                test::black_box(key.sign(&message));
+            });
+        });
+    }
 }
@@ -90,11 +90,11 @@ mod tests {
             let secret = format!("sealerSecret_z{}", bs58::encode(&private_key).into_string());
 
             // Get sealer ID
-            let sealer_id = get_sealer_id(&secret.as_bytes())?;
+            let sealer_id = get_sealer_id(secret.as_bytes())?;
             assert!(sealer_id.starts_with("sealer_z"));
 
             // Test that same secret produces same ID
-            let sealer_id2 = get_sealer_id(&secret.as_bytes())?;
+            let sealer_id2 = get_sealer_id(secret.as_bytes())?;
             assert_eq!(sealer_id, sealer_id2);
 
             // Test invalid secret format
@@ -0,0 +1,210 @@
+use crate::crypto::sign::get_signer_id_internal;
+use crate::crypto::x25519::get_sealer_id_internal;
+use crate::error::CryptoError;
+use crate::prefix::{
+    decode_prefixed_array, SEALER_ID_PREFIX, SEALER_SECRET_PREFIX, SIGNER_ID_PREFIX,
+};
+use ed25519_dalek::VerifyingKey;
+use wasm_bindgen::prelude::*;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Internal function to parse a "sealer_z" ID into a validated X25519 public key.
+/// Rejects points of small order, which would give a known shared secret.
+/// Returns the 32 public key bytes or CryptoError::InvalidPublicKey if the key is unusable.
+pub fn parse_sealer_id_internal(id: &str) -> Result<[u8; 32], CryptoError> {
+    let public_key = decode_prefixed_array::<32>(id, SEALER_ID_PREFIX, "sealer ID")?;
+    validate_x25519_public_key(&public_key)?;
+    Ok(public_key)
+}
+
+/// Check that an X25519 public key is not a point of small order.
+pub(crate) fn validate_x25519_public_key(public_key: &[u8; 32]) -> Result<(), CryptoError> {
+    // Every clamped scalar is a multiple of the cofactor, so the exchange
+    // only comes out all-zero when the point lies in the small-order subgroup.
+    let probe = StaticSecret::from([1u8; 32]);
+    if !probe
+        .diffie_hellman(&PublicKey::from(*public_key))
+        .was_contributory()
+    {
+        return Err(CryptoError::InvalidPublicKey("low-order point".to_string()));
+    }
+    Ok(())
+}
+
+/// Internal function to parse a "signer_z" ID into a validated Ed25519 verifying key.
+/// Rejects encodings that do not decompress to a curve point, and weak (small-order) keys.
+/// Returns the 32 verifying key bytes or CryptoError::InvalidVerifyingKey if the key is unusable.
+pub fn parse_signer_id_internal(id: &str) -> Result<[u8; 32], CryptoError> {
+    let key_bytes = decode_prefixed_array::<32>(id, SIGNER_ID_PREFIX, "signer ID")?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| CryptoError::InvalidVerifyingKey(e.to_string()))?;
+    if verifying_key.is_weak() {
+        return Err(CryptoError::InvalidVerifyingKey(
+            "low-order point".to_string(),
+        ));
+    }
+    Ok(key_bytes)
+}
+
+/// Internal function to validate an agent ID of the form "sealer_z.../signer_z...".
+/// Returns CryptoError if the format is wrong or either public key is invalid.
+pub fn validate_agent_id_internal(id: &str) -> Result<(), CryptoError> {
+    let (sealer_id, signer_id) = id
+        .split_once('/')
+        .ok_or(CryptoError::InvalidPrefix(SEALER_ID_PREFIX, "agent ID"))?;
+    parse_sealer_id_internal(sealer_id)?;
+    parse_signer_id_internal(signer_id)?;
+    Ok(())
+}
+
+/// Internal function to derive an agent ID from an agent secret.
+/// Takes "sealerSecret_z.../signerSecret_z..." and returns "sealer_z.../signer_z...",
+/// or CryptoError if the format is wrong or either secret is invalid.
+pub fn get_agent_id_internal(secret: &str) -> Result<String, CryptoError> {
+    let (sealer_secret, signer_secret) = secret.split_once('/').ok_or(
+        CryptoError::InvalidPrefix(SEALER_SECRET_PREFIX, "agent secret"),
+    )?;
+    Ok(format!(
+        "{}/{}",
+        get_sealer_id_internal(sealer_secret)?,
+        get_signer_id_internal(signer_secret)?
+    ))
+}
+
+/// WASM-exposed function to derive an agent ID from an agent secret.
+/// - `secret`: Agent secret of the form "sealerSecret_z.../signerSecret_z..."
+///
+/// Returns the agent ID "sealer_z.../signer_z..." or throws JsError if the secret is invalid.
+#[wasm_bindgen]
+pub fn get_agent_id(secret: &str) -> Result<String, JsError> {
+    Ok(get_agent_id_internal(secret)?)
+}
+
+/// WASM-exposed function to validate an agent ID.
+/// - `id`: Agent ID of the form "sealer_z.../signer_z..."
+///
+/// Returns nothing if both public keys are valid, or throws JsError describing the problem.
+#[wasm_bindgen]
+pub fn validate_agent_id(id: &str) -> Result<(), JsError> {
+    Ok(validate_agent_id_internal(id)?)
+}
+
+/// Generate a random agent secret, for tests that need a valid one.
+#[cfg(test)]
+pub(crate) fn test_agent_secret() -> String {
+    use crate::crypto::ed25519::new_ed25519_signing_key;
+    use crate::crypto::x25519::new_x25519_private_key;
+    use crate::prefix::{encode_prefixed, SIGNER_SECRET_PREFIX};
+    format!(
+        "{}/{}",
+        encode_prefixed(SEALER_SECRET_PREFIX, &new_x25519_private_key()),
+        encode_prefixed(SIGNER_SECRET_PREFIX, &new_ed25519_signing_key())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ed25519::{ed25519_verifying_key_internal, new_ed25519_signing_key};
+    use crate::crypto::x25519::{new_x25519_private_key, x25519_public_key_internal};
+    use crate::prefix::{encode_prefixed, SIGNER_SECRET_PREFIX};
+
+    fn agent_id() -> (String, String) {
+        let sealer_public = x25519_public_key_internal(&new_x25519_private_key()).unwrap();
+        let signer_public = ed25519_verifying_key_internal(&new_ed25519_signing_key()).unwrap();
+        (
+            encode_prefixed(SEALER_ID_PREFIX, &sealer_public),
+            encode_prefixed(SIGNER_ID_PREFIX, &signer_public),
+        )
+    }
+
+    #[test]
+    fn test_validate_agent_id() {
+        let (sealer_id, signer_id) = agent_id();
+        assert!(parse_sealer_id_internal(&sealer_id).is_ok());
+        assert!(parse_signer_id_internal(&signer_id).is_ok());
+        assert!(validate_agent_id_internal(&format!("{sealer_id}/{signer_id}")).is_ok());
+
+        // Swapped halves, missing separator and truncated keys
+        assert!(matches!(
+            validate_agent_id_internal(&format!("{signer_id}/{sealer_id}")),
+            Err(CryptoError::InvalidPrefix("sealer_z", "sealer ID"))
+        ));
+        assert!(validate_agent_id_internal(&sealer_id).is_err());
+        assert!(matches!(
+            parse_sealer_id_internal(&encode_prefixed(SEALER_ID_PREFIX, &[9u8; 31])),
+            Err(CryptoError::InvalidLength("sealer ID", 32, 31))
+        ));
+    }
+
+    #[test]
+    fn test_get_agent_id() {
+        let secret = test_agent_secret();
+        let (sealer, signer) = secret.split_once('/').unwrap();
+        let sealer_secret: [u8; 32] =
+            decode_prefixed_array(sealer, SEALER_SECRET_PREFIX, "sealer secret").unwrap();
+        let signer_secret: [u8; 32] =
+            decode_prefixed_array(signer, SIGNER_SECRET_PREFIX, "signer secret").unwrap();
+        let expected = format!(
+            "{}/{}",
+            encode_prefixed(
+                SEALER_ID_PREFIX,
+                &x25519_public_key_internal(&sealer_secret).unwrap()
+            ),
+            encode_prefixed(
+                SIGNER_ID_PREFIX,
+                &ed25519_verifying_key_internal(&signer_secret).unwrap()
+            )
+        );
+        let agent_id = get_agent_id_internal(&secret).unwrap();
+        assert_eq!(agent_id, expected);
+        assert!(validate_agent_id_internal(&agent_id).is_ok());
+
+        // Missing separator and swapped halves
+        assert!(matches!(
+            get_agent_id_internal("sealerSecret_z1111"),
+            Err(CryptoError::InvalidPrefix(
+                SEALER_SECRET_PREFIX,
+                "agent secret"
+            ))
+        ));
+        assert!(get_agent_id_internal(&format!("{signer}/{sealer}")).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_public_keys() {
+        // Small-order X25519 points: 0, 1 and a point of order 8
+        let mut order_8 = [0u8; 32];
+        order_8.copy_from_slice(&[
+            0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f,
+            0xc4, 0x6a, 0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16,
+            0x5f, 0x49, 0xb8, 0x00,
+        ]);
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        for point in [[0u8; 32], one, order_8] {
+            assert!(matches!(
+                parse_sealer_id_internal(&encode_prefixed(SEALER_ID_PREFIX, &point)),
+                Err(CryptoError::InvalidPublicKey(_))
+            ));
+        }
+
+        // y = 2 does not decompress to a point on the Edwards curve
+        let mut not_on_curve = [0u8; 32];
+        not_on_curve[0] = 2;
+        assert!(matches!(
+            parse_signer_id_internal(&encode_prefixed(SIGNER_ID_PREFIX, &not_on_curve)),
+            Err(CryptoError::InvalidVerifyingKey(_))
+        ));
+
+        // The Edwards identity (y = 1) is a weak key
+        assert!(matches!(
+            parse_signer_id_internal(&encode_prefixed(SIGNER_ID_PREFIX, &one)),
+            Err(CryptoError::InvalidVerifyingKey(_))
+        ));
+
+        let (sealer_id, _) = agent_id();
+        let garbage_signer = encode_prefixed(SIGNER_ID_PREFIX, &not_on_curve);
+        assert!(validate_agent_id_internal(&format!("{sealer_id}/{garbage_signer}")).is_err());
+    }
+}
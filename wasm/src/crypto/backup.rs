@@ -0,0 +1,265 @@
+use crate::crypto::agent::get_agent_id_internal;
+use crate::crypto::kdf::{
+    derive_secret_seed_from_passphrase_internal, KdfParams, DEFAULT_KDF_ITERATIONS,
+    DEFAULT_KDF_MEMORY_KIB,
+};
+use crate::crypto::xsalsa20::{decrypt_xsalsa20_poly1305, encrypt_xsalsa20_poly1305};
+use crate::error::CryptoError;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Magic bytes at the start of every agent secret export.
+const EXPORT_MAGIC: &[u8; 4] = b"JZAS";
+
+/// Current version of the export format.
+const EXPORT_VERSION: u8 = 1;
+
+/// Length of the random salt fed to Argon2id.
+const EXPORT_SALT_LENGTH: usize = 16;
+
+/// Length of the XSalsa20-Poly1305 nonce.
+const EXPORT_NONCE_LENGTH: usize = 24;
+
+/// Length of the header: magic, version, three u32 KDF costs, salt and nonce.
+const EXPORT_HEADER_LENGTH: usize = 4 + 1 + 3 * 4 + EXPORT_SALT_LENGTH + EXPORT_NONCE_LENGTH;
+
+/// Length of the BLAKE3 checksum closing the export.
+const EXPORT_CHECKSUM_LENGTH: usize = 32;
+
+/// Largest Argon2id memory cost accepted on import, four times the browser-safe default (256 MiB),
+/// so a crafted file can't exhaust memory before the password is even tried.
+const MAX_IMPORT_MEMORY_KIB: u32 = 4 * DEFAULT_KDF_MEMORY_KIB;
+
+/// Largest Argon2id pass count accepted on import, four times the default,
+/// so a crafted file can't hang the importer.
+const MAX_IMPORT_ITERATIONS: u32 = 4 * DEFAULT_KDF_ITERATIONS;
+
+/// Internal function to export an agent secret as a password-protected blob.
+/// - `secret`: Agent secret of the form "sealerSecret_z.../signerSecret_z..."
+/// - `password`: Password protecting the export
+/// - `params`: Argon2id costs used to derive the encryption key from the password
+///
+/// Returns the export blob or CryptoError if the agent secret or costs are invalid.
+///
+/// The layout is "JZAS", a version byte, the Argon2id memory, iterations and parallelism (u32 LE),
+/// a 16-byte salt, a 24-byte nonce, the XSalsa20-Poly1305 encrypted secret,
+/// and a BLAKE3 checksum over everything before it.
+/// The checksum tells a damaged file apart from a wrong password; authenticity comes from Poly1305.
+pub fn export_agent_secret_internal(
+    secret: &str,
+    password: &str,
+    params: &KdfParams,
+) -> Result<Vec<u8>, CryptoError> {
+    get_agent_id_internal(secret)?;
+
+    let mut salt = [0u8; EXPORT_SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; EXPORT_NONCE_LENGTH];
+    OsRng.fill_bytes(&mut nonce);
+    let key = derive_secret_seed_from_passphrase_internal(password.as_bytes(), &salt, params)?;
+    let ciphertext = encrypt_xsalsa20_poly1305(&*key, &nonce, secret.as_bytes())?;
+
+    let mut export =
+        Vec::with_capacity(EXPORT_HEADER_LENGTH + ciphertext.len() + EXPORT_CHECKSUM_LENGTH);
+    export.extend_from_slice(EXPORT_MAGIC);
+    export.push(EXPORT_VERSION);
+    export.extend_from_slice(&params.memory_kib().to_le_bytes());
+    export.extend_from_slice(&params.iterations().to_le_bytes());
+    export.extend_from_slice(&params.parallelism().to_le_bytes());
+    export.extend_from_slice(&salt);
+    export.extend_from_slice(&nonce);
+    export.extend_from_slice(&ciphertext);
+    let checksum = blake3::hash(&export);
+    export.extend_from_slice(checksum.as_bytes());
+    Ok(export)
+}
+
+/// Internal function to import an agent secret exported with `export_agent_secret_internal`.
+/// - `export`: The export blob
+/// - `password`: Password the export was protected with
+///
+/// Returns the agent secret, CryptoError::WrongPassword if the password doesn't open it,
+/// or CryptoError::CorruptExport if the blob is damaged or not an agent secret export.
+pub fn import_agent_secret_internal(export: &[u8], password: &str) -> Result<String, CryptoError> {
+    if export.len() < EXPORT_HEADER_LENGTH + EXPORT_CHECKSUM_LENGTH {
+        return Err(CryptoError::CorruptExport("truncated"));
+    }
+    let (body, checksum) = export.split_at(export.len() - EXPORT_CHECKSUM_LENGTH);
+    // blake3::Hash equality is constant-time
+    if blake3::hash(body) != blake3::Hash::from_bytes(checksum.try_into().unwrap()) {
+        return Err(CryptoError::CorruptExport("checksum mismatch"));
+    }
+
+    let (header, ciphertext) = body.split_at(EXPORT_HEADER_LENGTH);
+    if &header[..4] != EXPORT_MAGIC {
+        return Err(CryptoError::CorruptExport("not an agent secret export"));
+    }
+    if header[4] != EXPORT_VERSION {
+        return Err(CryptoError::CorruptExport("unsupported version"));
+    }
+    let cost = |offset: usize| u32::from_le_bytes(header[offset..offset + 4].try_into().unwrap());
+    let mut params = KdfParams::new();
+    params.set_memory_kib(cost(5));
+    params.set_iterations(cost(9));
+    params.set_parallelism(cost(13));
+    if params.memory_kib() > MAX_IMPORT_MEMORY_KIB || params.iterations() > MAX_IMPORT_ITERATIONS {
+        return Err(CryptoError::CorruptExport("KDF costs out of range"));
+    }
+    let salt = &header[17..17 + EXPORT_SALT_LENGTH];
+    let nonce = &header[17 + EXPORT_SALT_LENGTH..];
+
+    let key = derive_secret_seed_from_passphrase_internal(password.as_bytes(), salt, &params)
+        .map_err(|_| CryptoError::CorruptExport("invalid KDF parameters"))?;
+    // The checksum already ruled out damage, so a failed tag means the key is wrong
+    let secret = Zeroizing::new(decrypt_xsalsa20_poly1305(&*key, nonce, ciphertext).map_err(
+        |e| match e {
+            CryptoError::WrongTag => CryptoError::WrongPassword,
+            e => e,
+        },
+    )?);
+
+    let secret = std::str::from_utf8(&secret)
+        .map_err(|_| CryptoError::CorruptExport("agent secret is not UTF-8"))?;
+    get_agent_id_internal(secret)
+        .map_err(|_| CryptoError::CorruptExport("invalid agent secret"))?;
+    Ok(secret.to_string())
+}
+
+/// WASM-exposed function to export an agent secret as a password-protected blob.
+/// - `secret`: Agent secret of the form "sealerSecret_z.../signerSecret_z..."
+/// - `password`: Password protecting the export
+/// - `params`: Argon2id costs, or the browser-safe defaults if omitted
+///
+/// Returns the export blob or throws JsError if the agent secret is invalid.
+#[wasm_bindgen]
+pub fn export_agent_secret(
+    secret: &str,
+    password: &str,
+    params: Option<KdfParams>,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(export_agent_secret_internal(secret, password, &params.unwrap_or_default())?.into())
+}
+
+/// WASM-exposed function to import an agent secret from a password-protected blob.
+/// - `export`: The export blob
+/// - `password`: Password the export was protected with
+///
+/// Returns the agent secret or throws JsError for a wrong password or a corrupt export.
+#[wasm_bindgen]
+pub fn import_agent_secret(export: &[u8], password: &str) -> Result<String, JsError> {
+    Ok(import_agent_secret_internal(export, password)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::agent::test_agent_secret;
+    use crate::crypto::kdf::test_params;
+
+    /// Recompute the checksum after editing an export, to reach the checks behind it.
+    fn reseal(export: &mut [u8]) {
+        let body_len = export.len() - EXPORT_CHECKSUM_LENGTH;
+        let checksum = blake3::hash(&export[..body_len]);
+        export[body_len..].copy_from_slice(checksum.as_bytes());
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let secret = test_agent_secret();
+        let export = export_agent_secret_internal(&secret, "hunter2", &test_params()).unwrap();
+        assert!(export.starts_with(b"JZAS\x01"));
+        assert!(!export
+            .windows(secret.len())
+            .any(|window| window == secret.as_bytes()));
+        assert_eq!(
+            import_agent_secret_internal(&export, "hunter2").unwrap(),
+            secret
+        );
+
+        // Fresh salt and nonce on every export
+        let export2 = export_agent_secret_internal(&secret, "hunter2", &test_params()).unwrap();
+        assert_ne!(export, export2);
+        assert_eq!(
+            import_agent_secret_internal(&export2, "hunter2").unwrap(),
+            secret
+        );
+    }
+
+    #[test]
+    fn test_import_wrong_password() {
+        let export =
+            export_agent_secret_internal(&test_agent_secret(), "hunter2", &test_params()).unwrap();
+        assert!(matches!(
+            import_agent_secret_internal(&export, "hunter3"),
+            Err(CryptoError::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn test_import_corrupt_export() {
+        let export =
+            export_agent_secret_internal(&test_agent_secret(), "hunter2", &test_params()).unwrap();
+
+        // Any flipped bit or truncation is caught by the checksum before trying the password
+        for index in [0, 4, 5, 20, EXPORT_HEADER_LENGTH + 3, export.len() - 1] {
+            let mut damaged = export.clone();
+            damaged[index] ^= 1;
+            assert!(matches!(
+                import_agent_secret_internal(&damaged, "hunter2"),
+                Err(CryptoError::CorruptExport("checksum mismatch"))
+            ));
+        }
+        assert!(matches!(
+            import_agent_secret_internal(&export[..export.len() - 1], "hunter2"),
+            Err(CryptoError::CorruptExport("checksum mismatch"))
+        ));
+        assert!(matches!(
+            import_agent_secret_internal(&export[..40], "hunter2"),
+            Err(CryptoError::CorruptExport("truncated"))
+        ));
+
+        // Unknown magic or version
+        let mut other_magic = export.clone();
+        other_magic[0] = b'X';
+        reseal(&mut other_magic);
+        assert!(matches!(
+            import_agent_secret_internal(&other_magic, "hunter2"),
+            Err(CryptoError::CorruptExport("not an agent secret export"))
+        ));
+        let mut other_version = export.clone();
+        other_version[4] = 2;
+        reseal(&mut other_version);
+        assert!(matches!(
+            import_agent_secret_internal(&other_version, "hunter2"),
+            Err(CryptoError::CorruptExport("unsupported version"))
+        ));
+
+        // KDF costs beyond four times the browser-safe defaults are refused before deriving
+        for (offset, cost) in [
+            (5, u32::MAX),
+            (5, MAX_IMPORT_MEMORY_KIB + 1),
+            (9, MAX_IMPORT_ITERATIONS + 1),
+        ] {
+            let mut greedy = export.clone();
+            greedy[offset..offset + 4].copy_from_slice(&cost.to_le_bytes());
+            reseal(&mut greedy);
+            assert!(matches!(
+                import_agent_secret_internal(&greedy, "hunter2"),
+                Err(CryptoError::CorruptExport("KDF costs out of range"))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_export_rejects_invalid_secrets() {
+        for secret in [
+            "not an agent secret",
+            "sealerSecret_z1111/signerSecret_z1111",
+            "signerSecret_z1111/sealerSecret_z1111",
+        ] {
+            assert!(export_agent_secret_internal(secret, "hunter2", &test_params()).is_err());
+        }
+    }
+}
@@ -25,6 +25,7 @@ pub(crate) fn ed25519_verifying_key_internal(signing_key: &[u8]) -> Result<Box<[
 
 /// WASM-exposed function to derive an Ed25519 verifying key from a signing key.
 /// - `signing_key`: 32 bytes of signing key material
+///
 /// Returns 32 bytes of verifying key material or throws JsError if key is invalid.
 #[wasm_bindgen]
 pub fn ed25519_verifying_key(signing_key: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -48,6 +49,7 @@ pub(crate) fn ed25519_sign_internal(
 /// WASM-exposed function to sign a message using Ed25519.
 /// - `signing_key`: 32 bytes of signing key material
 /// - `message`: Raw bytes to sign
+///
 /// Returns 64 bytes of signature material or throws JsError if signing fails.
 #[wasm_bindgen]
 pub fn ed25519_sign(signing_key: &[u8], message: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -58,6 +60,7 @@ pub fn ed25519_sign(signing_key: &[u8], message: &[u8]) -> Result<Box<[u8]>, JsE
 /// - `verifying_key`: 32 bytes of verifying key material
 /// - `message`: Raw bytes that were signed
 /// - `signature`: 64 bytes of signature material
+///
 /// Returns true if signature is valid, false otherwise, or CryptoError if key/signature format is invalid.
 pub(crate) fn ed25519_verify_internal(
     verifying_key: &[u8],
@@ -82,6 +85,7 @@ pub(crate) fn ed25519_verify_internal(
 /// - `verifying_key`: 32 bytes of verifying key material
 /// - `message`: Raw bytes that were signed
 /// - `signature`: 64 bytes of signature material
+///
 /// Returns true if signature is valid, false otherwise, or throws JsError if verification fails.
 #[wasm_bindgen]
 pub fn ed25519_verify(
@@ -95,6 +99,7 @@ pub fn ed25519_verify(
 
 /// WASM-exposed function to validate and copy Ed25519 signing key bytes.
 /// - `bytes`: 32 bytes of signing key material to validate
+///
 /// Returns the same 32 bytes if valid or throws JsError if invalid.
 #[wasm_bindgen]
 pub fn ed25519_signing_key_from_bytes(bytes: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -106,6 +111,7 @@ pub fn ed25519_signing_key_from_bytes(bytes: &[u8]) -> Result<Box<[u8]>, JsError
 
 /// WASM-exposed function to derive the public key from an Ed25519 signing key.
 /// - `signing_key`: 32 bytes of signing key material
+///
 /// Returns 32 bytes of public key material or throws JsError if key is invalid.
 #[wasm_bindgen]
 pub fn ed25519_signing_key_to_public(signing_key: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -115,6 +121,7 @@ pub fn ed25519_signing_key_to_public(signing_key: &[u8]) -> Result<Box<[u8]>, Js
 /// WASM-exposed function to sign a message with an Ed25519 signing key.
 /// - `signing_key`: 32 bytes of signing key material
 /// - `message`: Raw bytes to sign
+///
 /// Returns 64 bytes of signature material or throws JsError if signing fails.
 #[wasm_bindgen]
 pub fn ed25519_signing_key_sign(signing_key: &[u8], message: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -123,6 +130,7 @@ pub fn ed25519_signing_key_sign(signing_key: &[u8], message: &[u8]) -> Result<Bo
 
 /// WASM-exposed function to validate and copy Ed25519 verifying key bytes.
 /// - `bytes`: 32 bytes of verifying key material to validate
+///
 /// Returns the same 32 bytes if valid or throws JsError if invalid.
 #[wasm_bindgen]
 pub fn ed25519_verifying_key_from_bytes(bytes: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -134,6 +142,7 @@ pub fn ed25519_verifying_key_from_bytes(bytes: &[u8]) -> Result<Box<[u8]>, JsErr
 
 /// WASM-exposed function to validate and copy Ed25519 signature bytes.
 /// - `bytes`: 64 bytes of signature material to validate
+///
 /// Returns the same 64 bytes if valid or throws JsError if invalid.
 #[wasm_bindgen]
 pub fn ed25519_signature_from_bytes(bytes: &[u8]) -> Result<Box<[u8]>, JsError> {
@@ -143,6 +152,56 @@ pub fn ed25519_signature_from_bytes(bytes: &[u8]) -> Result<Box<[u8]>, JsError>
     Ok(sig_bytes.into())
 }
 
+/// Internal function to convert an Ed25519 signing key into an X25519 private key.
+/// Takes 32 bytes of signing key material (the seed) and returns the clamped first half
+/// of its SHA-512 expansion, like libsodium's `crypto_sign_ed25519_sk_to_curve25519`.
+/// Returns CryptoError if the key length is invalid.
+pub(crate) fn ed25519_secret_to_x25519_internal(
+    signing_key: &[u8],
+) -> Result<[u8; 32], CryptoError> {
+    let key_bytes: [u8; 32] = signing_key
+        .try_into()
+        .map_err(|_| CryptoError::InvalidKeyLength(32, signing_key.len()))?;
+    let mut scalar = SigningKey::from_bytes(&key_bytes).to_scalar_bytes();
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    Ok(scalar)
+}
+
+/// Internal function to convert an Ed25519 verifying key into an X25519 public key.
+/// Applies the birational map from the Edwards curve to its Montgomery form,
+/// like libsodium's `crypto_sign_ed25519_pk_to_curve25519`.
+/// Returns CryptoError if the key length is invalid or the bytes are not a valid Edwards point.
+pub(crate) fn ed25519_public_to_x25519_internal(
+    verifying_key: &[u8],
+) -> Result<[u8; 32], CryptoError> {
+    let key_bytes: [u8; 32] = verifying_key
+        .try_into()
+        .map_err(|_| CryptoError::InvalidKeyLength(32, verifying_key.len()))?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| CryptoError::InvalidVerifyingKey(e.to_string()))?;
+    Ok(verifying_key.to_montgomery().to_bytes())
+}
+
+/// WASM-exposed function to convert an Ed25519 signing key into an X25519 private key.
+/// - `signing_key`: 32 bytes of signing key material
+///
+/// Returns 32 bytes of X25519 private key material or throws JsError if key is invalid.
+#[wasm_bindgen]
+pub fn ed25519_secret_to_x25519(signing_key: &[u8]) -> Result<Box<[u8]>, JsError> {
+    Ok(ed25519_secret_to_x25519_internal(signing_key)?.into())
+}
+
+/// WASM-exposed function to convert an Ed25519 verifying key into an X25519 public key.
+/// - `verifying_key`: 32 bytes of verifying key material
+///
+/// Returns 32 bytes of X25519 public key material or throws JsError if key is invalid.
+#[wasm_bindgen]
+pub fn ed25519_public_to_x25519(verifying_key: &[u8]) -> Result<Box<[u8]>, JsError> {
+    Ok(ed25519_public_to_x25519_internal(verifying_key)?.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,7 +255,7 @@ mod tests {
         );
 
         // Test verification with tampered signature
-        let mut tampered_signature = signature.clone();
+        let mut tampered_signature = signature;
         tampered_signature[0] ^= 1;
         let wrong_verification =
             ed25519_verify_internal(&verifying_key, message, &tampered_signature).unwrap();
@@ -237,4 +296,65 @@ mod tests {
         let result = ed25519_verify_internal(&valid_verifying_key, b"test", &too_long_signature);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_ed25519_to_x25519_libsodium_vectors() {
+        // From libsodium's test/default/ed25519_convert.c
+        let seed = hex::decode("421151a459faeade3d247115f94aedae42318124095afabe4d1451a559faedee")
+            .unwrap();
+        let expected_public =
+            hex::decode("f1814f0e8ff1043d8a44d25babff3cedcae6c22c3edaa48f857ae70de2baae50")
+                .unwrap();
+        let expected_secret =
+            hex::decode("8052030376d47112be7f73ed7a019293dd12ad910b654455798b4667d73de166")
+                .unwrap();
+
+        let verifying_key = ed25519_verifying_key_internal(&seed).unwrap();
+        assert_eq!(
+            ed25519_public_to_x25519_internal(&verifying_key)
+                .unwrap()
+                .to_vec(),
+            expected_public
+        );
+        assert_eq!(
+            ed25519_secret_to_x25519_internal(&seed).unwrap().to_vec(),
+            expected_secret
+        );
+    }
+
+    #[test]
+    fn test_ed25519_to_x25519_consistency() {
+        use crate::crypto::x25519::{x25519_diffie_hellman_internal, x25519_public_key_internal};
+
+        // The converted key pair is a valid X25519 key pair
+        let alice = new_ed25519_signing_key();
+        let bob = new_ed25519_signing_key();
+        let alice_x = ed25519_secret_to_x25519_internal(&alice).unwrap();
+        let bob_x = ed25519_secret_to_x25519_internal(&bob).unwrap();
+        let alice_x_public =
+            ed25519_public_to_x25519_internal(&ed25519_verifying_key_internal(&alice).unwrap())
+                .unwrap();
+        let bob_x_public =
+            ed25519_public_to_x25519_internal(&ed25519_verifying_key_internal(&bob).unwrap())
+                .unwrap();
+        assert_eq!(
+            x25519_public_key_internal(&alice_x).unwrap(),
+            alice_x_public
+        );
+
+        assert_eq!(
+            x25519_diffie_hellman_internal(&alice_x, &bob_x_public).unwrap(),
+            x25519_diffie_hellman_internal(&bob_x, &alice_x_public).unwrap()
+        );
+
+        // Invalid inputs
+        assert!(matches!(
+            ed25519_secret_to_x25519_internal(&[0u8; 31]),
+            Err(CryptoError::InvalidKeyLength(32, 31))
+        ));
+        assert!(matches!(
+            ed25519_public_to_x25519_internal(&[0u8; 31]),
+            Err(CryptoError::InvalidKeyLength(32, 31))
+        ));
+    }
 }
@@ -1,7 +1,81 @@
+use crate::crypto::envelope::{decode_envelope_with_options, encode_envelope, EnvelopeOptions};
 use crate::error::CryptoError;
-use crate::hash::blake3::generate_nonce;
+use crate::hash::blake3::{generate_nonce, transaction_nonce_material};
+use crate::json::stable_stringify::stable_stringify_internal;
+use crate::prefix::{
+    decode_prefixed_base64url, encode_prefixed, encode_prefixed_base64url, ENCRYPTED_PREFIX,
+    KEY_ID_PREFIX,
+};
 use bs58;
+use js_sys::{Array, Uint8Array};
+use rand::rngs::OsRng;
+use rand::RngCore;
 use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Number of bytes in a "key_z" key ID, matching cojson's randomly minted IDs.
+pub const KEY_ID_LENGTH: usize = 12;
+
+/// BLAKE3 derive_key context for key IDs committed to their key secret.
+const KEY_ID_CONTEXT: &str = "jazz-crypto-rs 2025 key ID";
+
+/// A freshly generated symmetric key secret together with its "key_z" ID.
+#[wasm_bindgen]
+pub struct KeySecretWithId {
+    id: String,
+    secret: String,
+}
+
+#[wasm_bindgen]
+impl KeySecretWithId {
+    /// The "key_z" ID of the key.
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    /// The "keySecret_z" secret of the key.
+    #[wasm_bindgen(getter)]
+    pub fn secret(&self) -> String {
+        self.secret.clone()
+    }
+}
+
+/// Internal function to generate a new random key secret and its ID.
+/// - `committed_id`: If true, the ID is derived from the secret with `key_id_from_secret_internal`.
+///   Otherwise it is 12 random bytes, as cojson mints it today.
+pub fn new_random_key_secret_internal(committed_id: bool) -> KeySecretWithId {
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    let secret = format!("keySecret_z{}", bs58::encode(key).into_string());
+
+    let id = if committed_id {
+        key_id_for_key(&key)
+    } else {
+        let mut id = [0u8; KEY_ID_LENGTH];
+        OsRng.fill_bytes(&mut id);
+        encode_prefixed(KEY_ID_PREFIX, &id)
+    };
+    KeySecretWithId { id, secret }
+}
+
+/// Internal function to derive the deterministic "key_z" ID of a key secret.
+/// The ID is a BLAKE3 derive_key commitment to the key, truncated to 12 bytes,
+/// so it can be checked against the secret without revealing it.
+/// Returns the ID or a CryptoError if the key secret is invalid.
+pub fn key_id_from_secret_internal(key_secret: &str) -> Result<String, CryptoError> {
+    let key = parse_key_secret(key_secret)?;
+    let key: [u8; 32] = key
+        .as_slice()
+        .try_into()
+        .map_err(|_| CryptoError::InvalidKeyLength(32, key.len()))?;
+    Ok(key_id_for_key(&key))
+}
+
+fn key_id_for_key(key: &[u8; 32]) -> String {
+    let commitment = blake3::derive_key(KEY_ID_CONTEXT, key);
+    encode_prefixed(KEY_ID_PREFIX, &commitment[..KEY_ID_LENGTH])
+}
 
 /// Internal function to encrypt bytes with a key secret and nonce material.
 /// Takes a base58-encoded key secret with "keySecret_z" prefix and raw nonce material.
@@ -11,19 +85,13 @@ pub fn encrypt_internal(
     key_secret: &str,
     nonce_material: &[u8],
 ) -> Result<Box<[u8]>, CryptoError> {
-    // Decode the base58 key secret (removing the "keySecret_z" prefix)
-    let key_secret = key_secret
-        .strip_prefix("keySecret_z")
-        .ok_or(CryptoError::InvalidPrefix("key secret", "keySecret_z"))?;
-    let key = bs58::decode(key_secret)
-        .into_vec()
-        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+    let key = parse_key_secret(key_secret)?;
 
     // Generate nonce from nonce material
     let nonce = generate_nonce(nonce_material);
 
     // Encrypt using XSalsa20
-    Ok(super::xsalsa20::encrypt_xsalsa20_raw_internal(&key, &nonce, plaintext)?.into())
+    super::xsalsa20::encrypt_xsalsa20_raw_internal(&key, &nonce, plaintext)
 }
 
 /// Internal function to decrypt bytes with a key secret and nonce material.
@@ -34,25 +102,238 @@ pub fn decrypt_internal(
     key_secret: &str,
     nonce_material: &[u8],
 ) -> Result<Box<[u8]>, CryptoError> {
-    // Decode the base58 key secret (removing the "keySecret_z" prefix)
+    let key = parse_key_secret(key_secret)?;
+    decrypt_with_key(ciphertext, &key, nonce_material)
+}
+
+/// Internal function to encrypt bytes wrapped in an envelope, e.g. to pad them.
+/// - `options`: Envelope options, recorded inside the ciphertext
+///
+/// Unlike `encrypt_internal`, the envelope is encrypted with XSalsa20-Poly1305,
+/// so its flags and padding can't be flipped to probe the plaintext through decoding errors.
+/// Returns the encrypted envelope or a CryptoError if the key format is invalid.
+pub fn encrypt_enveloped_internal(
+    plaintext: &[u8],
+    key_secret: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, CryptoError> {
+    let key = parse_key_secret(key_secret)?;
+    let nonce = generate_nonce(nonce_material);
+    super::xsalsa20::encrypt_xsalsa20_poly1305(&key, &nonce, &encode_envelope(plaintext, options))
+}
+
+/// Internal function to decrypt bytes encrypted with `encrypt_enveloped_internal`.
+/// Padding and compression are read from the decrypted envelope.
+/// Returns the original bytes or a CryptoError if the key or envelope is invalid.
+pub fn decrypt_enveloped_internal(
+    ciphertext: &[u8],
+    key_secret: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    decrypt_enveloped_with_options_internal(
+        ciphertext,
+        key_secret,
+        nonce_material,
+        &EnvelopeOptions::default(),
+    )
+}
+
+/// Internal function to decrypt an envelope like `decrypt_enveloped_internal`,
+/// with `options` limiting decompression.
+/// The envelope is only decoded once its Poly1305 tag checks out.
+/// Returns the original bytes, CryptoError::WrongTag if the ciphertext was tampered with,
+/// or another CryptoError if the key or envelope is invalid.
+pub fn decrypt_enveloped_with_options_internal(
+    ciphertext: &[u8],
+    key_secret: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, CryptoError> {
+    let key = parse_key_secret(key_secret)?;
+    let nonce = generate_nonce(nonce_material);
+    let envelope = super::xsalsa20::decrypt_xsalsa20_poly1305(&key, &nonce, ciphertext)?;
+    Ok(decode_envelope_with_options(&envelope, options)?.into())
+}
+
+/// Outcome of decrypting a single item in `decrypt_many_internal`.
+pub type DecryptResult = Result<Box<[u8]>, CryptoError>;
+
+/// Internal function to decrypt many ciphertexts with the same key secret.
+/// The key secret is parsed once and reused for every item.
+/// - `ciphertexts`: The encrypted values to decrypt
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `nonce_materials`: Nonce material for each ciphertext, in the same order
+///
+/// Returns one result per ciphertext, or a CryptoError if the key is invalid
+/// or the two lists have different lengths.
+pub fn decrypt_many_internal<C: AsRef<[u8]>, N: AsRef<[u8]>>(
+    ciphertexts: &[C],
+    key_secret: &str,
+    nonce_materials: &[N],
+) -> Result<Vec<DecryptResult>, CryptoError> {
+    if ciphertexts.len() != nonce_materials.len() {
+        return Err(CryptoError::InvalidLength(
+            "nonce materials",
+            ciphertexts.len(),
+            nonce_materials.len(),
+        ));
+    }
+    let key = parse_key_secret(key_secret)?;
+    if key.len() != 32 {
+        return Err(CryptoError::InvalidKeyLength(32, key.len()));
+    }
+
+    Ok(ciphertexts
+        .iter()
+        .zip(nonce_materials)
+        .map(|(ciphertext, nonce_material)| {
+            decrypt_with_key(ciphertext.as_ref(), &key, nonce_material.as_ref())
+        })
+        .collect())
+}
+
+/// Decode a base58 key secret, removing the "keySecret_z" prefix.
+pub(crate) fn parse_key_secret(key_secret: &str) -> Result<Vec<u8>, CryptoError> {
     let key_secret = key_secret
         .strip_prefix("keySecret_z")
         .ok_or(CryptoError::InvalidPrefix("key secret", "keySecret_z"))?;
-    let key = bs58::decode(key_secret)
+    bs58::decode(key_secret)
         .into_vec()
-        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+        .map_err(|e| CryptoError::Base58Error(e.to_string()))
+}
 
+/// Decrypt with an already decoded key, generating the nonce from nonce material.
+fn decrypt_with_key(
+    ciphertext: &[u8],
+    key: &[u8],
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
     // Generate nonce from nonce material
     let nonce = generate_nonce(nonce_material);
 
     // Decrypt using XSalsa20
-    Ok(super::xsalsa20::decrypt_xsalsa20_raw_internal(&key, &nonce, ciphertext)?.into())
+    super::xsalsa20::decrypt_xsalsa20_raw_internal(key, &nonce, ciphertext)
+}
+
+/// Internal function to encrypt the value of a private transaction.
+/// Derives the nonce from the transaction's CoValue ID, session ID and index, like cojson.
+/// Returns the encrypted bytes or a CryptoError if the key format is invalid.
+pub fn encrypt_for_transaction_internal(
+    value: &[u8],
+    key_secret: &str,
+    co_id: &str,
+    session_id: &str,
+    tx_index: u32,
+) -> Result<Box<[u8]>, CryptoError> {
+    let nonce_material = transaction_nonce_material(co_id, session_id, tx_index);
+    encrypt_internal(value, key_secret, &nonce_material)
+}
+
+/// Internal function to decrypt the value of a private transaction.
+/// Derives the nonce from the transaction's CoValue ID, session ID and index, like cojson.
+/// Returns the decrypted bytes or a CryptoError if the key format is invalid.
+pub fn decrypt_for_transaction_internal(
+    ciphertext: &[u8],
+    key_secret: &str,
+    co_id: &str,
+    session_id: &str,
+    tx_index: u32,
+) -> Result<Box<[u8]>, CryptoError> {
+    let nonce_material = transaction_nonce_material(co_id, session_id, tx_index);
+    decrypt_internal(ciphertext, key_secret, &nonce_material)
+}
+
+/// Build cojson's nonce material for wrapping one key secret with another.
+/// Returns the stable-stringified `{"encryptedID":encrypted_id,"encryptingID":encrypting_id}`.
+fn key_secret_nonce_material(
+    encrypted_id: &str,
+    encrypting_id: &str,
+) -> Result<Vec<u8>, CryptoError> {
+    for id in [encrypted_id, encrypting_id] {
+        if !id.starts_with(KEY_ID_PREFIX) {
+            return Err(CryptoError::InvalidPrefix(KEY_ID_PREFIX, "key ID"));
+        }
+    }
+    let material = serde_json::json!({
+        "encryptedID": encrypted_id,
+        "encryptingID": encrypting_id,
+    });
+    Ok(stable_stringify_internal(&material).into_bytes())
+}
+
+/// Internal function to encrypt one key secret under another, as done on group key rotation.
+/// The nonce material and the "encrypted_U" output format follow cojson's `encryptKeySecret`,
+/// but the secret is sealed with XSalsa20-Poly1305 so that a wrong unwrapping key is detected.
+/// - `to_encrypt_id`/`to_encrypt_secret`: The "key_z" ID and "keySecret_z" secret being wrapped
+/// - `encrypting_id`/`encrypting_secret`: The "key_z" ID and "keySecret_z" secret to wrap it with
+///
+/// Returns the "encrypted_U" string or a CryptoError if a key or ID is invalid.
+pub fn encrypt_key_secret_internal(
+    to_encrypt_id: &str,
+    to_encrypt_secret: &str,
+    encrypting_id: &str,
+    encrypting_secret: &str,
+) -> Result<String, CryptoError> {
+    // Make sure we only ever wrap well-formed key secrets
+    parse_key_secret(to_encrypt_secret)?;
+    let key = parse_key_secret(encrypting_secret)?;
+
+    let nonce = generate_nonce(&key_secret_nonce_material(to_encrypt_id, encrypting_id)?);
+    let plaintext = stable_stringify_internal(&serde_json::Value::from(to_encrypt_secret));
+    let ciphertext =
+        super::xsalsa20::encrypt_xsalsa20_poly1305(&key, &nonce, plaintext.as_bytes())?;
+    Ok(encode_prefixed_base64url(ENCRYPTED_PREFIX, &ciphertext))
+}
+
+/// Internal function to unwrap a key secret produced by `encrypt_key_secret_internal`.
+/// - `encrypted`: The "encrypted_U" string
+/// - `encrypted_id`: The "key_z" ID of the wrapped key
+/// - `encrypting_id`/`encrypting_secret`: The "key_z" ID and "keySecret_z" secret it was wrapped with
+///
+/// Returns the "keySecret_z" secret, or CryptoError::WrongTag if the unwrapping key or IDs are wrong.
+pub fn decrypt_key_secret_internal(
+    encrypted: &str,
+    encrypted_id: &str,
+    encrypting_id: &str,
+    encrypting_secret: &str,
+) -> Result<String, CryptoError> {
+    let key = parse_key_secret(encrypting_secret)?;
+    let ciphertext =
+        decode_prefixed_base64url(encrypted, ENCRYPTED_PREFIX, "encrypted key secret")?;
+
+    let nonce = generate_nonce(&key_secret_nonce_material(encrypted_id, encrypting_id)?);
+    let plaintext = super::xsalsa20::decrypt_xsalsa20_poly1305(&key, &nonce, &ciphertext)?;
+
+    let secret: String =
+        serde_json::from_slice(&plaintext).map_err(|e| CryptoError::InvalidJson(e.to_string()))?;
+    parse_key_secret(&secret)?;
+    Ok(secret)
+}
+
+/// WASM-exposed function to generate a new random key secret and its ID.
+/// - `committed_id`: If true, derive the ID from the secret instead of picking it at random
+///
+/// Returns an object with `id` ("key_z...") and `secret` ("keySecret_z...").
+#[wasm_bindgen]
+pub fn new_random_key_secret(committed_id: bool) -> KeySecretWithId {
+    new_random_key_secret_internal(committed_id)
+}
+
+/// WASM-exposed function to derive the deterministic "key_z" ID of a key secret.
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+///
+/// Returns the "key_z" ID or throws a JsError if the key secret is invalid.
+#[wasm_bindgen]
+pub fn key_id_from_secret(key_secret: &str) -> Result<String, JsError> {
+    Ok(key_id_from_secret_internal(key_secret)?)
 }
 
 /// WASM-exposed function to encrypt bytes with a key secret and nonce material.
 /// - `value`: The raw bytes to encrypt
 /// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
 /// - `nonce_material`: Raw bytes used to generate the nonce
+///
 /// Returns the encrypted bytes or throws a JsError if encryption fails.
 #[wasm_bindgen(js_name = encrypt)]
 pub fn encrypt(
@@ -67,6 +348,7 @@ pub fn encrypt(
 /// - `ciphertext`: The encrypted bytes to decrypt
 /// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
 /// - `nonce_material`: Raw bytes used to generate the nonce (must match encryption)
+///
 /// Returns the decrypted bytes or throws a JsError if decryption fails.
 #[wasm_bindgen(js_name = decrypt)]
 pub fn decrypt(
@@ -74,7 +356,289 @@ pub fn decrypt(
     key_secret: &str,
     nonce_material: &[u8],
 ) -> Result<Box<[u8]>, JsError> {
-    Ok(decrypt_internal(ciphertext, key_secret, nonce_material)?.into())
+    Ok(decrypt_internal(ciphertext, key_secret, nonce_material)?)
+}
+
+/// WASM-exposed function to encrypt bytes wrapped in an envelope.
+/// - `value`: The raw bytes to encrypt
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce
+/// - `options`: Envelope options such as padding and compression, recorded inside the ciphertext
+///
+/// Returns the encrypted bytes or throws a JsError if encryption fails.
+#[wasm_bindgen]
+pub fn encrypt_enveloped(
+    value: &[u8],
+    key_secret: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(encrypt_enveloped_internal(
+        value,
+        key_secret,
+        nonce_material,
+        options,
+    )?)
+}
+
+/// WASM-exposed function to decrypt bytes encrypted with `encrypt_enveloped`.
+/// - `ciphertext`: The encrypted envelope
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match encryption)
+///
+/// Returns the decrypted bytes or throws a JsError if decryption fails.
+#[wasm_bindgen]
+pub fn decrypt_enveloped(
+    ciphertext: &[u8],
+    key_secret: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    Ok(decrypt_enveloped_internal(
+        ciphertext,
+        key_secret,
+        nonce_material,
+    )?)
+}
+
+/// WASM-exposed function to decrypt bytes encrypted with `encrypt_enveloped`, with a custom decompression limit.
+/// - `ciphertext`: The encrypted envelope
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match encryption)
+/// - `options`: Envelope options; only the decompression limit applies when decrypting
+///
+/// Returns the decrypted bytes or throws a JsError if decryption fails.
+#[wasm_bindgen]
+pub fn decrypt_enveloped_with_options(
+    ciphertext: &[u8],
+    key_secret: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(decrypt_enveloped_with_options_internal(
+        ciphertext,
+        key_secret,
+        nonce_material,
+        options,
+    )?)
+}
+
+/// WASM-exposed function to decrypt many values with the same key secret in one call.
+/// - `ciphertexts`: Array of encrypted `Uint8Array`s
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `nonce_materials`: Array of nonce material `Uint8Array`s, one per ciphertext
+///
+/// Returns an Array holding, for each item, either the decrypted bytes or an Error.
+/// Throws a JsError if the key secret is invalid or the arrays differ in length.
+#[wasm_bindgen]
+pub fn decrypt_many(
+    ciphertexts: Vec<JsValue>,
+    key_secret: &str,
+    nonce_materials: Vec<JsValue>,
+) -> Result<Array, JsError> {
+    let to_bytes = |value: &JsValue| value.dyn_ref::<Uint8Array>().map(Uint8Array::to_vec);
+    let ciphertexts: Vec<Option<Vec<u8>>> = ciphertexts.iter().map(to_bytes).collect();
+    let nonce_materials: Vec<Option<Vec<u8>>> = nonce_materials.iter().map(to_bytes).collect();
+
+    // Items that are not byte arrays are decrypted as empty and reported as errors below
+    let decrypted = decrypt_many_internal(
+        &ciphertexts
+            .iter()
+            .map(|c| c.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>(),
+        key_secret,
+        &nonce_materials
+            .iter()
+            .map(|n| n.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>(),
+    )?;
+
+    Ok(decrypted
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            if ciphertexts[i].is_none() || nonce_materials[i].is_none() {
+                return JsValue::from(JsError::new(&format!("Item {i} is not a Uint8Array")));
+            }
+            match result {
+                Ok(plaintext) => Uint8Array::from(&*plaintext).into(),
+                Err(e) => JsError::new(&e.to_string()).into(),
+            }
+        })
+        .collect())
+}
+
+/// WASM-exposed function to encrypt the value of a private transaction.
+/// - `value`: The raw bytes to encrypt
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `co_id`: ID of the CoValue the transaction belongs to
+/// - `session_id`: ID of the session that made the transaction
+/// - `tx_index`: Index of the transaction within the session
+///
+/// Returns the encrypted bytes or throws a JsError if encryption fails.
+#[wasm_bindgen]
+pub fn encrypt_for_transaction(
+    value: &[u8],
+    key_secret: &str,
+    co_id: &str,
+    session_id: &str,
+    tx_index: u32,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(encrypt_for_transaction_internal(
+        value, key_secret, co_id, session_id, tx_index,
+    )?)
+}
+
+/// WASM-exposed function to decrypt the value of a private transaction.
+/// - `ciphertext`: The encrypted bytes to decrypt
+/// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+/// - `co_id`: ID of the CoValue the transaction belongs to
+/// - `session_id`: ID of the session that made the transaction
+/// - `tx_index`: Index of the transaction within the session
+///
+/// Returns the decrypted bytes or throws a JsError if decryption fails.
+#[wasm_bindgen]
+pub fn decrypt_for_transaction(
+    ciphertext: &[u8],
+    key_secret: &str,
+    co_id: &str,
+    session_id: &str,
+    tx_index: u32,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(decrypt_for_transaction_internal(
+        ciphertext, key_secret, co_id, session_id, tx_index,
+    )?)
+}
+
+/// WASM-exposed function to encrypt one key secret under another.
+/// - `to_encrypt_id`: The "key_z" ID of the key being wrapped
+/// - `to_encrypt_secret`: The "keySecret_z" secret being wrapped
+/// - `encrypting_id`: The "key_z" ID of the wrapping key
+/// - `encrypting_secret`: The "keySecret_z" secret of the wrapping key
+///
+/// Returns the "encrypted_U" string or throws a JsError if a key or ID is invalid.
+#[wasm_bindgen]
+pub fn encrypt_key_secret(
+    to_encrypt_id: &str,
+    to_encrypt_secret: &str,
+    encrypting_id: &str,
+    encrypting_secret: &str,
+) -> Result<String, JsError> {
+    Ok(encrypt_key_secret_internal(
+        to_encrypt_id,
+        to_encrypt_secret,
+        encrypting_id,
+        encrypting_secret,
+    )?)
+}
+
+/// WASM-exposed function to unwrap a key secret encrypted under another key.
+/// - `encrypted`: The "encrypted_U" string
+/// - `encrypted_id`: The "key_z" ID of the wrapped key
+/// - `encrypting_id`: The "key_z" ID of the wrapping key
+/// - `encrypting_secret`: The "keySecret_z" secret of the wrapping key
+///
+/// Returns the "keySecret_z" secret or throws a JsError if unwrapping fails.
+#[wasm_bindgen]
+pub fn decrypt_key_secret(
+    encrypted: &str,
+    encrypted_id: &str,
+    encrypting_id: &str,
+    encrypting_secret: &str,
+) -> Result<String, JsError> {
+    Ok(decrypt_key_secret_internal(
+        encrypted,
+        encrypted_id,
+        encrypting_id,
+        encrypting_secret,
+    )?)
+}
+
+/// A symmetric key secret parsed once for repeated encryption and decryption.
+/// The decoded key is zeroized on drop.
+#[wasm_bindgen]
+pub struct SymmetricKey {
+    key: Zeroizing<[u8; 32]>,
+}
+
+#[wasm_bindgen]
+impl SymmetricKey {
+    /// Parse a key secret.
+    /// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+    #[wasm_bindgen(constructor)]
+    pub fn new(key_secret: &str) -> Result<SymmetricKey, JsError> {
+        Ok(Self::new_internal(key_secret)?)
+    }
+
+    /// Encrypt bytes, same as `encrypt`.
+    /// - `value`: The raw bytes to encrypt
+    /// - `nonce_material`: Raw bytes used to generate the nonce
+    pub fn encrypt(&self, value: &[u8], nonce_material: &[u8]) -> Result<Box<[u8]>, JsError> {
+        Ok(self.encrypt_internal(value, nonce_material)?)
+    }
+
+    /// Decrypt bytes, same as `decrypt`.
+    /// - `ciphertext`: The encrypted bytes to decrypt
+    /// - `nonce_material`: Raw bytes used to generate the nonce (must match encryption)
+    pub fn decrypt(&self, ciphertext: &[u8], nonce_material: &[u8]) -> Result<Box<[u8]>, JsError> {
+        Ok(self.decrypt_internal(ciphertext, nonce_material)?)
+    }
+
+    /// Encrypt the value of a private transaction, same as `encrypt_for_transaction`.
+    pub fn encrypt_for_transaction(
+        &self,
+        value: &[u8],
+        co_id: &str,
+        session_id: &str,
+        tx_index: u32,
+    ) -> Result<Box<[u8]>, JsError> {
+        let nonce_material = transaction_nonce_material(co_id, session_id, tx_index);
+        Ok(self.encrypt_internal(value, &nonce_material)?)
+    }
+
+    /// Decrypt the value of a private transaction, same as `decrypt_for_transaction`.
+    pub fn decrypt_for_transaction(
+        &self,
+        ciphertext: &[u8],
+        co_id: &str,
+        session_id: &str,
+        tx_index: u32,
+    ) -> Result<Box<[u8]>, JsError> {
+        let nonce_material = transaction_nonce_material(co_id, session_id, tx_index);
+        Ok(self.decrypt_internal(ciphertext, &nonce_material)?)
+    }
+}
+
+impl SymmetricKey {
+    /// Internal constructor from a "keySecret_z" string.
+    pub fn new_internal(key_secret: &str) -> Result<Self, CryptoError> {
+        let key = Zeroizing::new(parse_key_secret(key_secret)?);
+        let key: [u8; 32] = key
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidKeyLength(32, key.len()))?;
+        Ok(Self {
+            key: Zeroizing::new(key),
+        })
+    }
+
+    /// Internal function to encrypt bytes with this key.
+    pub fn encrypt_internal(
+        &self,
+        plaintext: &[u8],
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, CryptoError> {
+        let nonce = generate_nonce(nonce_material);
+        super::xsalsa20::encrypt_xsalsa20_raw_internal(&*self.key, &nonce, plaintext)
+    }
+
+    /// Internal function to decrypt bytes with this key.
+    pub fn decrypt_internal(
+        &self,
+        ciphertext: &[u8],
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, CryptoError> {
+        decrypt_with_key(ciphertext, &*self.key, nonce_material)
+    }
 }
 
 #[cfg(test)]
@@ -110,4 +674,279 @@ mod tests {
         let result = encrypt_internal(plaintext, "keySecret_z!!!!", nonce_material);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_encrypt_for_transaction() {
+        let plaintext = b"{\"op\":\"set\",\"key\":\"a\",\"value\":1}";
+        let key_secret = "keySecret_z11111111111111111111111111111111";
+        let co_id = "co_zTestCoValue";
+        let session_id = "sealer_zA/signer_zB_session_zC";
+
+        // Nonce material matches cojson's stableStringify({ in, tx })
+        let nonce_material =
+            br#"{"in":"co_zTestCoValue","tx":{"sessionID":"sealer_zA/signer_zB_session_zC","txIndex":7}}"#;
+        assert_eq!(
+            transaction_nonce_material(co_id, session_id, 7),
+            nonce_material.to_vec()
+        );
+
+        let ciphertext =
+            encrypt_for_transaction_internal(plaintext, key_secret, co_id, session_id, 7).unwrap();
+        assert_eq!(
+            ciphertext,
+            encrypt_internal(plaintext, key_secret, nonce_material).unwrap()
+        );
+
+        let decrypted =
+            decrypt_for_transaction_internal(&ciphertext, key_secret, co_id, session_id, 7)
+                .unwrap();
+        assert_eq!(&*decrypted, plaintext);
+
+        // A different transaction index yields a different keystream
+        let other = decrypt_for_transaction_internal(&ciphertext, key_secret, co_id, session_id, 8)
+            .unwrap();
+        assert_ne!(&*other, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_many() {
+        let key_secret = "keySecret_z11111111111111111111111111111111";
+        let plaintexts: Vec<Vec<u8>> = (0..5).map(|i| format!("value {i}").into_bytes()).collect();
+        let nonce_materials: Vec<Vec<u8>> =
+            (0..5).map(|i| format!("nonce {i}").into_bytes()).collect();
+        let ciphertexts: Vec<Box<[u8]>> = plaintexts
+            .iter()
+            .zip(&nonce_materials)
+            .map(|(p, n)| encrypt_internal(p, key_secret, n).unwrap())
+            .collect();
+
+        let decrypted = decrypt_many_internal(&ciphertexts, key_secret, &nonce_materials).unwrap();
+        assert_eq!(decrypted.len(), 5);
+        for (result, plaintext) in decrypted.into_iter().zip(&plaintexts) {
+            assert_eq!(&*result.unwrap(), &plaintext[..]);
+        }
+
+        // Mismatched lengths
+        assert!(matches!(
+            decrypt_many_internal(&ciphertexts, key_secret, &nonce_materials[..4]),
+            Err(CryptoError::InvalidLength("nonce materials", 5, 4))
+        ));
+
+        // Invalid key fails the whole batch
+        assert!(decrypt_many_internal(&ciphertexts, "invalid_key", &nonce_materials).is_err());
+        assert!(matches!(
+            decrypt_many_internal(&ciphertexts, "keySecret_z1111", &nonce_materials),
+            Err(CryptoError::InvalidKeyLength(32, _))
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_key_secret() {
+        let old_secret = format!("keySecret_z{}", bs58::encode([1u8; 32]).into_string());
+        let new_secret = format!("keySecret_z{}", bs58::encode([2u8; 32]).into_string());
+        let other_secret = format!("keySecret_z{}", bs58::encode([3u8; 32]).into_string());
+
+        // Nonce material matches cojson's stableStringify({ encryptedID, encryptingID })
+        assert_eq!(
+            key_secret_nonce_material("key_zOld", "key_zNew").unwrap(),
+            br#"{"encryptedID":"key_zOld","encryptingID":"key_zNew"}"#.to_vec()
+        );
+
+        let encrypted =
+            encrypt_key_secret_internal("key_zOld", &old_secret, "key_zNew", &new_secret).unwrap();
+        assert!(encrypted.starts_with("encrypted_U"));
+
+        let decrypted =
+            decrypt_key_secret_internal(&encrypted, "key_zOld", "key_zNew", &new_secret).unwrap();
+        assert_eq!(decrypted, old_secret);
+
+        // Wrong unwrapping key is detected
+        assert!(matches!(
+            decrypt_key_secret_internal(&encrypted, "key_zOld", "key_zNew", &other_secret),
+            Err(CryptoError::WrongTag)
+        ));
+
+        // Swapped IDs change the nonce and are detected too
+        assert!(matches!(
+            decrypt_key_secret_internal(&encrypted, "key_zNew", "key_zOld", &new_secret),
+            Err(CryptoError::WrongTag)
+        ));
+
+        // Malformed inputs
+        assert!(matches!(
+            encrypt_key_secret_internal("oldKey", &old_secret, "key_zNew", &new_secret),
+            Err(CryptoError::InvalidPrefix("key_z", "key ID"))
+        ));
+        assert!(
+            encrypt_key_secret_internal("key_zOld", "notASecret", "key_zNew", &new_secret).is_err()
+        );
+        assert!(matches!(
+            decrypt_key_secret_internal(&encrypted[1..], "key_zOld", "key_zNew", &new_secret),
+            Err(CryptoError::InvalidPrefix(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_new_random_key_secret() {
+        use crate::prefix::decode_prefixed;
+
+        let random = new_random_key_secret_internal(false);
+        assert!(random.secret().starts_with("keySecret_z"));
+        assert_eq!(
+            decode_prefixed(&random.id(), "key_z", "key ID")
+                .unwrap()
+                .len(),
+            KEY_ID_LENGTH
+        );
+        // The secret is usable for encryption
+        let ciphertext = encrypt_internal(b"hello", &random.secret(), b"nonce").unwrap();
+        assert_eq!(
+            &*decrypt_internal(&ciphertext, &random.secret(), b"nonce").unwrap(),
+            b"hello"
+        );
+
+        let other = new_random_key_secret_internal(false);
+        assert_ne!(random.id(), other.id());
+        assert_ne!(random.secret(), other.secret());
+
+        // Committed IDs can be recomputed from the secret
+        let committed = new_random_key_secret_internal(true);
+        assert_eq!(
+            key_id_from_secret_internal(&committed.secret()).unwrap(),
+            committed.id()
+        );
+        assert_ne!(
+            key_id_from_secret_internal(&random.secret()).unwrap(),
+            key_id_from_secret_internal(&other.secret()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_key_id_from_secret() {
+        let key_secret = "keySecret_z11111111111111111111111111111111";
+        let id = key_id_from_secret_internal(key_secret).unwrap();
+        assert_eq!(
+            id,
+            format!(
+                "key_z{}",
+                bs58::encode(&blake3::derive_key(KEY_ID_CONTEXT, &[0u8; 32])[..12]).into_string()
+            )
+        );
+        // Deterministic
+        assert_eq!(key_id_from_secret_internal(key_secret).unwrap(), id);
+
+        assert!(key_id_from_secret_internal("invalid_key").is_err());
+        assert!(matches!(
+            key_id_from_secret_internal("keySecret_z1111"),
+            Err(CryptoError::InvalidKeyLength(32, _))
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_enveloped() {
+        use crate::crypto::envelope::Padding;
+
+        let key_secret = "keySecret_z11111111111111111111111111111111";
+        let nonce_material = b"test_nonce_material";
+        let mut options = EnvelopeOptions::new();
+        options.set_padding(Padding::PowerOfTwo);
+
+        let short =
+            encrypt_enveloped_internal(b"yes", key_secret, nonce_material, &options).unwrap();
+        let long =
+            encrypt_enveloped_internal(b"no way", key_secret, nonce_material, &options).unwrap();
+        assert_eq!(short.len(), long.len());
+
+        assert_eq!(
+            &*decrypt_enveloped_internal(&short, key_secret, nonce_material).unwrap(),
+            b"yes"
+        );
+        assert_eq!(
+            &*decrypt_enveloped_internal(&long, key_secret, nonce_material).unwrap(),
+            b"no way"
+        );
+
+        // Unpadded envelopes decrypt the same way
+        let plain = encrypt_enveloped_internal(
+            b"no way",
+            key_secret,
+            nonce_material,
+            &EnvelopeOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(plain.len(), 7 + 16);
+        assert_eq!(
+            &*decrypt_enveloped_internal(&plain, key_secret, nonce_material).unwrap(),
+            b"no way"
+        );
+
+        // The envelope is authenticated, so flipping the padding bits of its flags byte
+        // (right after the 16-byte tag) is caught before any unpadding happens
+        let mut tampered = short.to_vec();
+        tampered[16] ^= 0b0000_0011;
+        assert!(matches!(
+            decrypt_enveloped_internal(&tampered, key_secret, nonce_material),
+            Err(CryptoError::WrongTag)
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_enveloped_compressed() {
+        let key_secret = "keySecret_z11111111111111111111111111111111";
+        let nonce_material = b"test_nonce_material";
+        let mut options = EnvelopeOptions::new();
+        options.set_compression(true);
+
+        let text = "compressible ".repeat(100);
+        let ciphertext =
+            encrypt_enveloped_internal(text.as_bytes(), key_secret, nonce_material, &options)
+                .unwrap();
+        assert!(ciphertext.len() < text.len());
+        assert_eq!(
+            &*decrypt_enveloped_internal(&ciphertext, key_secret, nonce_material).unwrap(),
+            text.as_bytes()
+        );
+
+        // Turning the compression flag off (or on) is caught by the tag,
+        // so LZ4 never runs on bytes an attacker has shaped
+        for ciphertext in [
+            ciphertext,
+            encrypt_enveloped_internal(
+                b"plain",
+                key_secret,
+                nonce_material,
+                &EnvelopeOptions::new(),
+            )
+            .unwrap(),
+        ] {
+            let mut tampered = ciphertext.to_vec();
+            tampered[16] ^= 0b0000_0100;
+            assert!(matches!(
+                decrypt_enveloped_internal(&tampered, key_secret, nonce_material),
+                Err(CryptoError::WrongTag)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_symmetric_key() {
+        let key_secret = "keySecret_z11111111111111111111111111111111";
+        let key = SymmetricKey::new_internal(key_secret).unwrap();
+
+        let ciphertext = key.encrypt_internal(b"Hello, World!", b"nonce").unwrap();
+        assert_eq!(
+            ciphertext,
+            encrypt_internal(b"Hello, World!", key_secret, b"nonce").unwrap()
+        );
+        assert_eq!(
+            &*key.decrypt_internal(&ciphertext, b"nonce").unwrap(),
+            b"Hello, World!"
+        );
+
+        assert!(SymmetricKey::new_internal("invalid_key").is_err());
+        assert!(matches!(
+            SymmetricKey::new_internal("keySecret_z1111"),
+            Err(CryptoError::InvalidKeyLength(32, _))
+        ));
+    }
 }
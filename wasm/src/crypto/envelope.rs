@@ -0,0 +1,330 @@
+use crate::error::CryptoError;
+use wasm_bindgen::prelude::*;
+
+/// Bits of the flags byte holding the padding scheme.
+const FLAG_PADDING_MASK: u8 = 0b0000_0011;
+/// Flag bit set when the payload is LZ4-compressed.
+const FLAG_COMPRESSED: u8 = 0b0000_0100;
+/// All flag bits understood by this version of the envelope.
+const KNOWN_FLAGS: u8 = FLAG_PADDING_MASK | FLAG_COMPRESSED;
+
+/// Default cap on the decompressed size of a payload, to guard against decompression bombs.
+pub const DEFAULT_MAX_DECOMPRESSED_LEN: usize = 16 * 1024 * 1024;
+
+/// Marker byte separating the payload from the zero padding.
+const PADDING_MARKER: u8 = 0x80;
+
+/// Length-hiding padding scheme applied to a plaintext before encryption.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Padding {
+    /// No padding, the ciphertext reveals the exact plaintext length.
+    #[default]
+    None = 0,
+    /// PADMÉ padding, which leaks O(log log n) bits of the length with at most 12% overhead.
+    Padme = 1,
+    /// Padding to the next power of two, which leaks O(log n) bits with up to 100% overhead.
+    PowerOfTwo = 2,
+}
+
+impl Padding {
+    fn from_flags(flags: u8) -> Result<Self, CryptoError> {
+        match flags & FLAG_PADDING_MASK {
+            0 => Ok(Padding::None),
+            1 => Ok(Padding::Padme),
+            2 => Ok(Padding::PowerOfTwo),
+            _ => Err(CryptoError::InvalidEnvelope("unknown padding scheme")),
+        }
+    }
+
+    /// Total length a padded envelope of `len` bytes is extended to.
+    fn padded_len(self, len: usize) -> usize {
+        match self {
+            Padding::None => len,
+            Padding::Padme => padme_len(len),
+            Padding::PowerOfTwo => len.next_power_of_two(),
+        }
+    }
+}
+
+/// Options for the envelope wrapped around a plaintext by the `*_enveloped` functions.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct EnvelopeOptions {
+    padding: Padding,
+    compression: bool,
+    max_decompressed_len: usize,
+}
+
+impl Default for EnvelopeOptions {
+    fn default() -> Self {
+        Self {
+            padding: Padding::None,
+            compression: false,
+            max_decompressed_len: DEFAULT_MAX_DECOMPRESSED_LEN,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl EnvelopeOptions {
+    /// Create options with every stage disabled.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to LZ4-compress the plaintext before padding and encryption.
+    /// Disabled by default: compressing secrets next to attacker-influenced data
+    /// leaks information through the ciphertext length (CRIME/BREACH-style attacks).
+    #[wasm_bindgen(getter)]
+    pub fn compression(&self) -> bool {
+        self.compression
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_compression(&mut self, compression: bool) {
+        self.compression = compression;
+    }
+
+    /// Largest decompressed size accepted when opening a compressed envelope.
+    #[wasm_bindgen(getter)]
+    pub fn max_decompressed_len(&self) -> usize {
+        self.max_decompressed_len
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_decompressed_len(&mut self, max_decompressed_len: usize) {
+        self.max_decompressed_len = max_decompressed_len;
+    }
+
+    /// The padding scheme to apply.
+    #[wasm_bindgen(getter)]
+    pub fn padding(&self) -> Padding {
+        self.padding
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_padding(&mut self, padding: Padding) {
+        self.padding = padding;
+    }
+}
+
+/// Compute the PADMÉ length for `len` bytes.
+/// Only the top floor(log2(floor(log2(len)))) + 1 bits of the length are kept,
+/// rounding up by zeroing the lower bits.
+fn padme_len(len: usize) -> usize {
+    if len < 2 {
+        return len;
+    }
+    let exponent = usize::BITS - 1 - len.leading_zeros();
+    let significant_bits = u32::BITS - exponent.leading_zeros();
+    let mask = (1usize << (exponent - significant_bits)) - 1;
+    (len + mask) & !mask
+}
+
+/// Wrap a plaintext in an envelope before encryption.
+/// The envelope is a flags byte recording the options, followed by the plaintext.
+/// When compression is enabled, the plaintext is stored as its length (u32 LE) and an LZ4 block.
+/// When padding is enabled, a 0x80 marker and zero bytes extend the envelope
+/// to the padded length, so unpadding is unambiguous for any plaintext.
+pub fn encode_envelope(plaintext: &[u8], options: &EnvelopeOptions) -> Vec<u8> {
+    let mut flags = options.padding as u8;
+    if options.compression {
+        flags |= FLAG_COMPRESSED;
+    }
+
+    let mut envelope = Vec::with_capacity(plaintext.len() + 2);
+    envelope.push(flags);
+    if options.compression {
+        envelope.extend_from_slice(&lz4_flex::block::compress_prepend_size(plaintext));
+    } else {
+        envelope.extend_from_slice(plaintext);
+    }
+
+    if options.padding != Padding::None {
+        envelope.push(PADDING_MARKER);
+        let padded_len = options.padding.padded_len(envelope.len());
+        envelope.resize(padded_len, 0);
+    }
+    envelope
+}
+
+/// Unwrap a decrypted envelope produced by `encode_envelope`.
+/// Padding and compression are read from the envelope's flags, and decompression
+/// is bounded by the default limit.
+/// Returns the original plaintext or CryptoError::InvalidEnvelope if the envelope is malformed.
+pub fn decode_envelope(envelope: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    decode_envelope_with_options(envelope, &EnvelopeOptions::default())
+}
+
+/// Unwrap a decrypted envelope like `decode_envelope`, with a custom decompression limit.
+/// Of the options, only `max_decompressed_len` is used.
+/// Returns the original plaintext or CryptoError::InvalidEnvelope if the envelope is malformed.
+pub fn decode_envelope_with_options(
+    envelope: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Vec<u8>, CryptoError> {
+    let (&flags, body) = envelope
+        .split_first()
+        .ok_or(CryptoError::InvalidEnvelope("empty envelope"))?;
+    if flags & !KNOWN_FLAGS != 0 {
+        return Err(CryptoError::InvalidEnvelope("unknown flags"));
+    }
+
+    let body = match Padding::from_flags(flags)? {
+        Padding::None => body,
+        _ => {
+            let marker = body
+                .iter()
+                .rposition(|&b| b != 0)
+                .ok_or(CryptoError::InvalidEnvelope("missing padding marker"))?;
+            if body[marker] != PADDING_MARKER {
+                return Err(CryptoError::InvalidEnvelope("missing padding marker"));
+            }
+            &body[..marker]
+        }
+    };
+
+    if flags & FLAG_COMPRESSED == 0 {
+        return Ok(body.to_vec());
+    }
+    if body.len() < 4 {
+        return Err(CryptoError::InvalidEnvelope("truncated compressed payload"));
+    }
+    let (len, compressed) = body.split_at(4);
+    let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+    if len > options.max_decompressed_len {
+        return Err(CryptoError::InvalidEnvelope(
+            "decompressed size exceeds limit",
+        ));
+    }
+    lz4_flex::block::decompress(compressed, len)
+        .map_err(|_| CryptoError::InvalidEnvelope("invalid compressed payload"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(padding: Padding) -> EnvelopeOptions {
+        let mut options = EnvelopeOptions::new();
+        options.set_padding(padding);
+        options
+    }
+
+    #[test]
+    fn test_padme_len() {
+        assert_eq!(padme_len(0), 0);
+        assert_eq!(padme_len(1), 1);
+        assert_eq!(padme_len(9), 10);
+        assert_eq!(padme_len(1000), 1024);
+        assert_eq!(padme_len(1025), 1088);
+
+        // Never shrinks and stays within 12% overhead at every length
+        for len in 0..1 << 16 {
+            let padded = padme_len(len);
+            assert!(padded >= len);
+            assert!(padded * 100 <= len * 112);
+        }
+    }
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        for padding in [Padding::None, Padding::Padme, Padding::PowerOfTwo] {
+            let options = options(padding);
+            for len in [0, 1, 5, 15, 16, 100, 1000] {
+                // Trailing 0x80 and zero bytes in the plaintext must survive unpadding
+                let mut plaintext = vec![0x42u8; len];
+                if len >= 2 {
+                    plaintext[len - 2] = PADDING_MARKER;
+                    plaintext[len - 1] = 0;
+                }
+                let envelope = encode_envelope(&plaintext, &options);
+                assert_eq!(decode_envelope(&envelope).unwrap(), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_hides_length() {
+        let pow2 = options(Padding::PowerOfTwo);
+        let lengths: Vec<usize> = (3..=6)
+            .map(|len| encode_envelope(&vec![1u8; len], &pow2).len())
+            .collect();
+        assert!(lengths.iter().all(|&len| len == 8));
+
+        let padme = options(Padding::Padme);
+        assert_eq!(
+            encode_envelope(&[1u8; 995], &padme).len(),
+            encode_envelope(&[1u8; 1000], &padme).len()
+        );
+
+        // Without padding only the flags byte is added
+        assert_eq!(encode_envelope(&[1u8; 5], &EnvelopeOptions::new()).len(), 6);
+    }
+
+    #[test]
+    fn test_invalid_envelopes() {
+        assert!(matches!(
+            decode_envelope(&[]),
+            Err(CryptoError::InvalidEnvelope(_))
+        ));
+        // Unknown flag bits and padding scheme
+        assert!(decode_envelope(&[0b1000_0000, 1, 2]).is_err());
+        assert!(decode_envelope(&[3, 1, 2]).is_err());
+        // Padded envelope without a marker
+        assert!(decode_envelope(&[1, 0, 0, 0]).is_err());
+        assert!(decode_envelope(&[1, 5, 7, 0]).is_err());
+        // Truncated or corrupt compressed payload
+        assert!(decode_envelope(&[FLAG_COMPRESSED, 1, 0]).is_err());
+        assert!(decode_envelope(&[FLAG_COMPRESSED, 10, 0, 0, 0, 0xff]).is_err());
+    }
+
+    #[test]
+    fn test_compression() {
+        let text = "the quick brown fox jumps over the lazy dog. ".repeat(200);
+        for padding in [Padding::None, Padding::Padme] {
+            let mut options = options(padding);
+            options.set_compression(true);
+            let envelope = encode_envelope(text.as_bytes(), &options);
+            assert!(envelope.len() < text.len() / 4);
+            assert_eq!(decode_envelope(&envelope).unwrap(), text.as_bytes());
+        }
+
+        // Compression is off by default
+        assert!(!EnvelopeOptions::new().compression());
+        assert_eq!(
+            encode_envelope(text.as_bytes(), &EnvelopeOptions::new()).len(),
+            text.len() + 1
+        );
+    }
+
+    #[test]
+    fn test_decompression_limit() {
+        let mut options = EnvelopeOptions::new();
+        options.set_compression(true);
+        let bomb = encode_envelope(&vec![0u8; 1 << 20], &options);
+        assert!(bomb.len() < 8 * 1024);
+
+        let mut limited = EnvelopeOptions::new();
+        limited.set_max_decompressed_len(1 << 16);
+        assert!(matches!(
+            decode_envelope_with_options(&bomb, &limited),
+            Err(CryptoError::InvalidEnvelope(
+                "decompressed size exceeds limit"
+            ))
+        ));
+        assert_eq!(decode_envelope(&bomb).unwrap().len(), 1 << 20);
+
+        // A length header claiming more than the default limit is rejected before decompressing
+        let mut forged = vec![FLAG_COMPRESSED];
+        forged.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            decode_envelope(&forged),
+            Err(CryptoError::InvalidEnvelope(
+                "decompressed size exceeds limit"
+            ))
+        ));
+    }
+}
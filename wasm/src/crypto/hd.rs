@@ -0,0 +1,202 @@
+use crate::crypto::kdf::SECRET_SEED_LENGTH;
+use crate::error::CryptoError;
+use crate::prefix::{encode_prefixed, SEALER_SECRET_PREFIX, SIGNER_SECRET_PREFIX};
+use ed25519_dalek::SigningKey;
+use wasm_bindgen::prelude::*;
+use x25519_dalek::StaticSecret;
+use zeroize::Zeroizing;
+
+/// BLAKE3 derive_key context for stepping from a node to its child along a derivation path.
+const HD_CHILD_CONTEXT: &str = "jazz-crypto-rs 2025 HD child key";
+
+/// BLAKE3 derive_key context for the X25519 sealer secret at a node.
+const HD_SEALER_CONTEXT: &str = "jazz-crypto-rs 2025 HD sealer secret";
+
+/// BLAKE3 derive_key context for the Ed25519 signer secret at a node.
+const HD_SIGNER_CONTEXT: &str = "jazz-crypto-rs 2025 HD signer secret";
+
+/// Walk a derivation path such as "account/device/3" down from the root seed.
+/// Each segment is hashed together with its parent's key, so every node depends on the whole path.
+fn derive_node(
+    root_seed: &[u8],
+    path: &str,
+) -> Result<Zeroizing<[u8; SECRET_SEED_LENGTH]>, CryptoError> {
+    let mut node = Zeroizing::new(<[u8; SECRET_SEED_LENGTH]>::try_from(root_seed).map_err(
+        |_| CryptoError::InvalidLength("root seed", SECRET_SEED_LENGTH, root_seed.len()),
+    )?);
+    if path.is_empty() {
+        return Err(CryptoError::InvalidDerivationPath("path is empty"));
+    }
+    for segment in path.split('/') {
+        if segment.is_empty() {
+            return Err(CryptoError::InvalidDerivationPath("empty path segment"));
+        }
+        // The parent key is fixed-length, so parent and segment can't run into each other
+        let mut hasher = blake3::Hasher::new_derive_key(HD_CHILD_CONTEXT);
+        hasher.update(&*node);
+        hasher.update(segment.as_bytes());
+        *node = *hasher.finalize().as_bytes();
+    }
+    Ok(node)
+}
+
+/// Internal function to derive a sealer secret from a root seed and a derivation path.
+/// - `root_seed`: 32-byte root seed, such as one recovered from a mnemonic
+/// - `path`: Non-empty, '/'-separated path such as "account/device/3"
+///
+/// Returns "sealerSecret_z..." or CryptoError if the seed length or path is invalid.
+pub fn derive_sealer_secret_internal(root_seed: &[u8], path: &str) -> Result<String, CryptoError> {
+    let node = derive_node(root_seed, path)?;
+    let secret = StaticSecret::from(blake3::derive_key(HD_SEALER_CONTEXT, &*node));
+    Ok(encode_prefixed(SEALER_SECRET_PREFIX, secret.as_bytes()))
+}
+
+/// Internal function to derive a signer secret from a root seed and a derivation path.
+/// - `root_seed`: 32-byte root seed, such as one recovered from a mnemonic
+/// - `path`: Non-empty, '/'-separated path such as "account/device/3"
+///
+/// Returns "signerSecret_z..." or CryptoError if the seed length or path is invalid.
+pub fn derive_signer_secret_internal(root_seed: &[u8], path: &str) -> Result<String, CryptoError> {
+    let node = derive_node(root_seed, path)?;
+    let signing_key = SigningKey::from_bytes(&blake3::derive_key(HD_SIGNER_CONTEXT, &*node));
+    Ok(encode_prefixed(
+        SIGNER_SECRET_PREFIX,
+        signing_key.as_bytes(),
+    ))
+}
+
+/// Internal function to derive a full agent secret from a root seed and a derivation path.
+/// The sealer and signer secrets both come from the node at `path`, under separate contexts,
+/// so one backup of the root seed rebuilds the agent of every device.
+/// Returns "sealerSecret_z.../signerSecret_z..." or CryptoError if the seed length or path is invalid.
+pub fn derive_agent_secret_internal(root_seed: &[u8], path: &str) -> Result<String, CryptoError> {
+    Ok(format!(
+        "{}/{}",
+        derive_sealer_secret_internal(root_seed, path)?,
+        derive_signer_secret_internal(root_seed, path)?
+    ))
+}
+
+/// WASM-exposed function to derive a sealer secret along a derivation path.
+/// - `root_seed`: 32-byte root seed
+/// - `path`: Derivation path such as "account/device/3"
+///
+/// Returns "sealerSecret_z..." or throws JsError if the seed or path is invalid.
+#[wasm_bindgen]
+pub fn derive_sealer_secret(root_seed: &[u8], path: &str) -> Result<String, JsError> {
+    Ok(derive_sealer_secret_internal(root_seed, path)?)
+}
+
+/// WASM-exposed function to derive a signer secret along a derivation path.
+/// - `root_seed`: 32-byte root seed
+/// - `path`: Derivation path such as "account/device/3"
+///
+/// Returns "signerSecret_z..." or throws JsError if the seed or path is invalid.
+#[wasm_bindgen]
+pub fn derive_signer_secret(root_seed: &[u8], path: &str) -> Result<String, JsError> {
+    Ok(derive_signer_secret_internal(root_seed, path)?)
+}
+
+/// WASM-exposed function to derive a full agent secret along a derivation path.
+/// - `root_seed`: 32-byte root seed
+/// - `path`: Derivation path such as "account/device/3"
+///
+/// Returns "sealerSecret_z.../signerSecret_z..." or throws JsError if the seed or path is invalid.
+#[wasm_bindgen]
+pub fn derive_agent_secret(root_seed: &[u8], path: &str) -> Result<String, JsError> {
+    Ok(derive_agent_secret_internal(root_seed, path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::agent::get_agent_id_internal;
+    use crate::crypto::seal::decode_sealer_secret;
+    use crate::crypto::sign::{get_signer_id_internal, sign_internal, verify_internal};
+    use crate::crypto::x25519::{x25519_diffie_hellman_internal, x25519_public_key_internal};
+
+    const ROOT_SEED: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn test_derivation_is_deterministic_and_path_bound() {
+        let agent = derive_agent_secret_internal(&ROOT_SEED, "account/device/3").unwrap();
+        assert_eq!(
+            agent,
+            derive_agent_secret_internal(&ROOT_SEED, "account/device/3").unwrap()
+        );
+        let (sealer, signer) = agent.split_once('/').unwrap();
+        assert_eq!(
+            sealer,
+            derive_sealer_secret_internal(&ROOT_SEED, "account/device/3").unwrap()
+        );
+        assert_eq!(
+            signer,
+            derive_signer_secret_internal(&ROOT_SEED, "account/device/3").unwrap()
+        );
+
+        // Sibling indices, parents, re-split segments and other roots give unrelated agents
+        let mut other_seed = ROOT_SEED;
+        other_seed[0] ^= 1;
+        for other in [
+            derive_agent_secret_internal(&ROOT_SEED, "account/device/4").unwrap(),
+            derive_agent_secret_internal(&ROOT_SEED, "account/device").unwrap(),
+            derive_agent_secret_internal(&ROOT_SEED, "account/device3").unwrap(),
+            derive_agent_secret_internal(&ROOT_SEED, "account/devi/ce/3").unwrap(),
+            derive_agent_secret_internal(&other_seed, "account/device/3").unwrap(),
+        ] {
+            let (other_sealer, other_signer) = other.split_once('/').unwrap();
+            assert_ne!(sealer, other_sealer);
+            assert_ne!(signer, other_signer);
+        }
+
+        // The sealer and signer at a node don't share key bytes
+        assert_ne!(
+            sealer.strip_prefix(SEALER_SECRET_PREFIX),
+            signer.strip_prefix(SIGNER_SECRET_PREFIX)
+        );
+    }
+
+    #[test]
+    fn test_derived_agent_is_usable() {
+        let alice = derive_agent_secret_internal(&ROOT_SEED, "account/device/1").unwrap();
+        let bob = derive_agent_secret_internal(&ROOT_SEED, "account/device/2").unwrap();
+        let (alice_sealer, alice_signer) = alice.split_once('/').unwrap();
+        let (bob_sealer, _) = bob.split_once('/').unwrap();
+        assert!(get_agent_id_internal(&alice).is_ok());
+
+        let message = b"hello device";
+        let signature = sign_internal(message, alice_signer).unwrap();
+        let alice_signer_id = get_signer_id_internal(alice_signer).unwrap();
+        assert!(verify_internal(&signature, message, &alice_signer_id).unwrap());
+
+        // Two derived devices agree on a sealing key
+        let alice_private = decode_sealer_secret(alice_sealer).unwrap();
+        let bob_private = decode_sealer_secret(bob_sealer).unwrap();
+        assert_eq!(
+            x25519_diffie_hellman_internal(
+                &alice_private,
+                &x25519_public_key_internal(&bob_private).unwrap()
+            )
+            .unwrap(),
+            x25519_diffie_hellman_internal(
+                &bob_private,
+                &x25519_public_key_internal(&alice_private).unwrap()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_derivation_inputs() {
+        for path in ["", "/account", "account/", "account//device"] {
+            assert!(matches!(
+                derive_agent_secret_internal(&ROOT_SEED, path),
+                Err(CryptoError::InvalidDerivationPath(_))
+            ));
+        }
+        assert!(matches!(
+            derive_sealer_secret_internal(&[0u8; 16], "account"),
+            Err(CryptoError::InvalidLength("root seed", 32, 16))
+        ));
+    }
+}
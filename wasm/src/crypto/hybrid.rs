@@ -0,0 +1,392 @@
+use crate::crypto::agent::{parse_sealer_id_internal, validate_x25519_public_key};
+use crate::crypto::seal::decode_sealer_secret;
+use crate::crypto::x25519::{x25519_diffie_hellman_internal, x25519_public_key_internal};
+use crate::crypto::xsalsa20::{decrypt_xsalsa20_poly1305, encrypt_xsalsa20_poly1305};
+use crate::error::CryptoError;
+use crate::hash::blake3::generate_nonce;
+use crate::prefix::{
+    decode_prefixed, decode_prefixed_array, encode_prefixed, HYBRID_SEALER_ID_PREFIX,
+    HYBRID_SEALER_SECRET_PREFIX,
+};
+use ml_kem::kem::{Decapsulate, Encapsulate};
+use ml_kem::{Ciphertext, EncodedSizeUser, KemCore, MlKem768, B32};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use wasm_bindgen::prelude::*;
+use x25519_dalek::StaticSecret;
+use zeroize::Zeroizing;
+
+/// BLAKE3 derive_key context for combining the X25519 and ML-KEM shared secrets.
+const HYBRID_SEALING_CONTEXT: &str = "jazz-crypto-rs 2025 hybrid sealing key";
+
+/// Length of a hybrid sealer secret: the X25519 private key followed by the ML-KEM-768 seed (d, z).
+pub const HYBRID_SEALER_SECRET_LENGTH: usize = 32 + 64;
+
+/// Length of an ML-KEM-768 encapsulation key.
+const ML_KEM_ENCAPSULATION_KEY_LENGTH: usize = 1184;
+
+/// Length of a hybrid sealer ID: the X25519 public key followed by the ML-KEM-768 encapsulation key.
+pub const HYBRID_SEALER_ID_LENGTH: usize = 32 + ML_KEM_ENCAPSULATION_KEY_LENGTH;
+
+/// Length of the ML-KEM-768 ciphertext prepended to every hybrid-sealed message.
+pub const ML_KEM_CIPHERTEXT_LENGTH: usize = 1088;
+
+type DecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+type EncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+
+/// A decoded hybrid sealer secret.
+struct HybridSealerSecret {
+    x25519_private_key: Zeroizing<[u8; 32]>,
+    decapsulation_key: DecapsulationKey,
+    encapsulation_key: EncapsulationKey,
+}
+
+/// Decode a "hybridSealerSecret_z" string and expand its ML-KEM seed into a key pair.
+fn decode_hybrid_sealer_secret(secret: &str) -> Result<HybridSealerSecret, CryptoError> {
+    let bytes = Zeroizing::new(decode_prefixed(
+        secret,
+        HYBRID_SEALER_SECRET_PREFIX,
+        "hybrid sealer secret",
+    )?);
+    if bytes.len() != HYBRID_SEALER_SECRET_LENGTH {
+        return Err(CryptoError::InvalidLength(
+            "hybrid sealer secret",
+            HYBRID_SEALER_SECRET_LENGTH,
+            bytes.len(),
+        ));
+    }
+
+    let mut x25519_private_key = Zeroizing::new([0u8; 32]);
+    x25519_private_key.copy_from_slice(&bytes[..32]);
+    let d = B32::try_from(&bytes[32..64]).unwrap();
+    let z = B32::try_from(&bytes[64..]).unwrap();
+    let (decapsulation_key, encapsulation_key) = MlKem768::generate_deterministic(&d, &z);
+
+    Ok(HybridSealerSecret {
+        x25519_private_key,
+        decapsulation_key,
+        encapsulation_key,
+    })
+}
+
+/// Decode a "hybridSealer_z" string into its validated X25519 public key and ML-KEM encapsulation key.
+fn decode_hybrid_sealer_id(id: &str) -> Result<([u8; 32], EncapsulationKey), CryptoError> {
+    let bytes = decode_prefixed_array::<HYBRID_SEALER_ID_LENGTH>(
+        id,
+        HYBRID_SEALER_ID_PREFIX,
+        "hybrid sealer ID",
+    )?;
+
+    let mut x25519_public_key = [0u8; 32];
+    x25519_public_key.copy_from_slice(&bytes[..32]);
+    validate_x25519_public_key(&x25519_public_key)?;
+
+    let encoded = ml_kem::Encoded::<EncapsulationKey>::try_from(&bytes[32..]).unwrap();
+    Ok((x25519_public_key, EncapsulationKey::from_bytes(&encoded)))
+}
+
+/// Combine both shared secrets into a single XSalsa20-Poly1305 key.
+/// The ML-KEM ciphertext and both X25519 public keys are bound into the key as well,
+/// so the result stays secret as long as either X25519 or ML-KEM is unbroken.
+fn hybrid_sealing_key(
+    x25519_shared_secret: &[u8; 32],
+    ml_kem_shared_secret: &[u8],
+    ml_kem_ciphertext: &[u8],
+    sender_public_key: &[u8; 32],
+    recipient_public_key: &[u8; 32],
+) -> Zeroizing<[u8; 32]> {
+    let mut hasher = blake3::Hasher::new_derive_key(HYBRID_SEALING_CONTEXT);
+    hasher.update(ml_kem_shared_secret);
+    hasher.update(x25519_shared_secret);
+    hasher.update(ml_kem_ciphertext);
+    hasher.update(sender_public_key);
+    hasher.update(recipient_public_key);
+    Zeroizing::new(*hasher.finalize().as_bytes())
+}
+
+/// Generate a new hybrid X25519 + ML-KEM-768 sealer secret.
+/// Returns a base58-encoded secret with "hybridSealerSecret_z" prefix.
+#[wasm_bindgen]
+pub fn new_hybrid_sealer_secret() -> String {
+    let mut bytes = Zeroizing::new([0u8; HYBRID_SEALER_SECRET_LENGTH]);
+    bytes[..32].copy_from_slice(StaticSecret::random().as_bytes());
+    OsRng.fill_bytes(&mut bytes[32..]);
+    encode_prefixed(HYBRID_SEALER_SECRET_PREFIX, &*bytes)
+}
+
+/// Internal function to derive a hybrid sealer ID from a hybrid sealer secret.
+/// Returns a base58-encoded ID with "hybridSealer_z" prefix or CryptoError if the secret is invalid.
+pub fn get_hybrid_sealer_id_internal(secret: &str) -> Result<String, CryptoError> {
+    let secret = decode_hybrid_sealer_secret(secret)?;
+    let mut id = Vec::with_capacity(HYBRID_SEALER_ID_LENGTH);
+    id.extend_from_slice(&x25519_public_key_internal(&*secret.x25519_private_key)?);
+    id.extend_from_slice(&secret.encapsulation_key.as_bytes());
+    Ok(encode_prefixed(HYBRID_SEALER_ID_PREFIX, &id))
+}
+
+/// WASM-exposed function to derive a hybrid sealer ID from a hybrid sealer secret.
+/// - `secret`: Base58-encoded secret with "hybridSealerSecret_z" prefix
+///
+/// Returns a base58-encoded ID with "hybridSealer_z" prefix or throws JsError if the secret is invalid.
+#[wasm_bindgen]
+pub fn get_hybrid_sealer_id(secret: &str) -> Result<String, JsError> {
+    Ok(get_hybrid_sealer_id_internal(secret)?)
+}
+
+/// Internal function to seal a message for a hybrid recipient using X25519 + ML-KEM-768.
+/// - `message`: Raw bytes to seal
+/// - `sender_secret`: Base58-encoded sender's private key with "sealerSecret_z" prefix
+/// - `recipient_id`: Base58-encoded recipient's hybrid public key with "hybridSealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce
+///
+/// Returns the ML-KEM ciphertext followed by the XSalsa20-Poly1305 ciphertext,
+/// or CryptoError if key formats are invalid.
+///
+/// The sender stays authenticated through the static X25519 exchange, as with `seal`,
+/// while the ML-KEM encapsulation protects the message against a future quantum adversary.
+pub fn seal_hybrid_internal(
+    message: &[u8],
+    sender_secret: &str,
+    recipient_id: &str,
+    nonce_material: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let sender_private_key = decode_sealer_secret(sender_secret)?;
+    let sender_public_key = x25519_public_key_internal(&sender_private_key)?;
+    let (recipient_public_key, encapsulation_key) = decode_hybrid_sealer_id(recipient_id)?;
+
+    let x25519_shared_secret = Zeroizing::new(x25519_diffie_hellman_internal(
+        &sender_private_key,
+        &recipient_public_key,
+    )?);
+    let (ml_kem_ciphertext, ml_kem_shared_secret) = encapsulation_key
+        .encapsulate(&mut OsRng)
+        .map_err(|_| CryptoError::CipherError)?;
+    let ml_kem_shared_secret = Zeroizing::new(<[u8; 32]>::from(ml_kem_shared_secret));
+
+    let key = hybrid_sealing_key(
+        &x25519_shared_secret,
+        &*ml_kem_shared_secret,
+        &ml_kem_ciphertext,
+        &sender_public_key,
+        &recipient_public_key,
+    );
+    let nonce = generate_nonce(nonce_material);
+
+    let mut sealed = Vec::with_capacity(ML_KEM_CIPHERTEXT_LENGTH + message.len() + 16);
+    sealed.extend_from_slice(&ml_kem_ciphertext);
+    sealed.extend_from_slice(&encrypt_xsalsa20_poly1305(&*key, &nonce, message)?);
+    Ok(sealed)
+}
+
+/// Internal function to unseal a message sealed with `seal_hybrid_internal`.
+/// - `sealed_message`: The ML-KEM ciphertext followed by the XSalsa20-Poly1305 ciphertext
+/// - `recipient_secret`: Base58-encoded recipient's hybrid private key with "hybridSealerSecret_z" prefix
+/// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+///
+/// Returns unsealed bytes or CryptoError if key formats are invalid or authentication fails.
+pub fn unseal_hybrid_internal(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    if sealed_message.len() < ML_KEM_CIPHERTEXT_LENGTH {
+        return Err(CryptoError::InvalidLength(
+            "hybrid sealed message",
+            ML_KEM_CIPHERTEXT_LENGTH,
+            sealed_message.len(),
+        ));
+    }
+    let (ml_kem_ciphertext, ciphertext) = sealed_message.split_at(ML_KEM_CIPHERTEXT_LENGTH);
+
+    let recipient = decode_hybrid_sealer_secret(recipient_secret)?;
+    let recipient_public_key = x25519_public_key_internal(&*recipient.x25519_private_key)?;
+    let sender_public_key = parse_sealer_id_internal(sender_id)?;
+
+    let x25519_shared_secret = Zeroizing::new(x25519_diffie_hellman_internal(
+        &*recipient.x25519_private_key,
+        &sender_public_key,
+    )?);
+    let ml_kem_ciphertext_array = Ciphertext::<MlKem768>::try_from(ml_kem_ciphertext).unwrap();
+    let ml_kem_shared_secret = recipient
+        .decapsulation_key
+        .decapsulate(&ml_kem_ciphertext_array)
+        .map_err(|_| CryptoError::CipherError)?;
+    let ml_kem_shared_secret = Zeroizing::new(<[u8; 32]>::from(ml_kem_shared_secret));
+
+    let key = hybrid_sealing_key(
+        &x25519_shared_secret,
+        &*ml_kem_shared_secret,
+        ml_kem_ciphertext,
+        &sender_public_key,
+        &recipient_public_key,
+    );
+    let nonce = generate_nonce(nonce_material);
+    decrypt_xsalsa20_poly1305(&*key, &nonce, ciphertext)
+}
+
+/// WASM-exposed function for sealing a message with hybrid X25519 + ML-KEM-768 key agreement.
+/// - `message`: Raw bytes to seal
+/// - `sender_secret`: Base58-encoded sender's private key with "sealerSecret_z" prefix
+/// - `recipient_id`: Base58-encoded recipient's hybrid public key with "hybridSealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce
+///
+/// Returns sealed bytes or throws JsError if sealing fails.
+#[wasm_bindgen]
+pub fn seal_hybrid(
+    message: &[u8],
+    sender_secret: &str,
+    recipient_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    Ok(seal_hybrid_internal(message, sender_secret, recipient_id, nonce_material)?.into())
+}
+
+/// WASM-exposed function for unsealing a message sealed with `seal_hybrid`.
+/// - `sealed_message`: The sealed bytes to decrypt
+/// - `recipient_secret`: Base58-encoded recipient's hybrid private key with "hybridSealerSecret_z" prefix
+/// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+///
+/// Returns unsealed bytes or throws JsError if unsealing fails.
+#[wasm_bindgen]
+pub fn unseal_hybrid(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    Ok(unseal_hybrid_internal(
+        sealed_message,
+        recipient_secret,
+        sender_id,
+        nonce_material,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seal::seal_internal;
+    use crate::crypto::x25519::{get_sealer_id_internal, new_x25519_private_key};
+
+    fn sealer() -> (String, String) {
+        let secret = encode_prefixed("sealerSecret_z", &new_x25519_private_key());
+        let id = get_sealer_id_internal(&secret).unwrap();
+        (secret, id)
+    }
+
+    #[test]
+    fn test_hybrid_sealer_keys() {
+        let secret = new_hybrid_sealer_secret();
+        assert!(secret.starts_with(HYBRID_SEALER_SECRET_PREFIX));
+
+        // The ID is deterministic in the secret
+        let id = get_hybrid_sealer_id_internal(&secret).unwrap();
+        assert!(id.starts_with(HYBRID_SEALER_ID_PREFIX));
+        assert_eq!(id, get_hybrid_sealer_id_internal(&secret).unwrap());
+        assert_ne!(
+            id,
+            get_hybrid_sealer_id_internal(&new_hybrid_sealer_secret()).unwrap()
+        );
+
+        // Classic and hybrid keys are not interchangeable
+        let (classic_secret, _) = sealer();
+        assert!(matches!(
+            get_hybrid_sealer_id_internal(&classic_secret),
+            Err(CryptoError::InvalidPrefix(_, _))
+        ));
+        assert!(matches!(
+            get_hybrid_sealer_id_internal(&encode_prefixed(
+                HYBRID_SEALER_SECRET_PREFIX,
+                &[1u8; 32]
+            )),
+            Err(CryptoError::InvalidLength("hybrid sealer secret", 96, 32))
+        ));
+    }
+
+    #[test]
+    fn test_seal_unseal_hybrid() {
+        let (sender_secret, sender_id) = sealer();
+        let recipient_secret = new_hybrid_sealer_secret();
+        let recipient_id = get_hybrid_sealer_id_internal(&recipient_secret).unwrap();
+        let message = b"group key";
+        let nonce_material = b"nonce";
+
+        let sealed =
+            seal_hybrid_internal(message, &sender_secret, &recipient_id, nonce_material).unwrap();
+        assert_eq!(sealed.len(), ML_KEM_CIPHERTEXT_LENGTH + message.len() + 16);
+        let unsealed =
+            unseal_hybrid_internal(&sealed, &recipient_secret, &sender_id, nonce_material).unwrap();
+        assert_eq!(&*unsealed, message);
+
+        // Encapsulation is randomized, so sealing twice gives different ciphertexts
+        let sealed_again =
+            seal_hybrid_internal(message, &sender_secret, &recipient_id, nonce_material).unwrap();
+        assert_ne!(sealed, sealed_again);
+
+        // Wrong sender, recipient or nonce material
+        let (_, other_sender_id) = sealer();
+        assert!(unseal_hybrid_internal(
+            &sealed,
+            &recipient_secret,
+            &other_sender_id,
+            nonce_material
+        )
+        .is_err());
+        let other_recipient = new_hybrid_sealer_secret();
+        assert!(
+            unseal_hybrid_internal(&sealed, &other_recipient, &sender_id, nonce_material).is_err()
+        );
+        assert!(unseal_hybrid_internal(&sealed, &recipient_secret, &sender_id, b"other").is_err());
+
+        // Tampering with either the ML-KEM or the XSalsa20-Poly1305 ciphertext
+        for index in [0, ML_KEM_CIPHERTEXT_LENGTH + 1] {
+            let mut tampered = sealed.clone();
+            tampered[index] ^= 1;
+            assert!(matches!(
+                unseal_hybrid_internal(&tampered, &recipient_secret, &sender_id, nonce_material),
+                Err(CryptoError::WrongTag)
+            ));
+        }
+
+        // Truncated below the ML-KEM ciphertext
+        assert!(matches!(
+            unseal_hybrid_internal(
+                &sealed[..100],
+                &recipient_secret,
+                &sender_id,
+                nonce_material
+            ),
+            Err(CryptoError::InvalidLength("hybrid sealed message", _, 100))
+        ));
+    }
+
+    #[test]
+    fn test_hybrid_sealing_alongside_classic() {
+        let (sender_secret, _) = sealer();
+        let (_, recipient_id) = sealer();
+        let nonce_material = b"nonce";
+
+        // The same sender secret keeps working for classic sealing
+        assert!(seal_internal(b"hello", &sender_secret, &recipient_id, nonce_material).is_ok());
+
+        // A classic sealer ID can't be used as a hybrid recipient
+        assert!(matches!(
+            seal_hybrid_internal(b"hello", &sender_secret, &recipient_id, nonce_material),
+            Err(CryptoError::InvalidPrefix(HYBRID_SEALER_ID_PREFIX, _))
+        ));
+
+        // A hybrid ID with a low-order X25519 part is rejected
+        let hybrid_id = get_hybrid_sealer_id_internal(&new_hybrid_sealer_secret()).unwrap();
+        let mut bytes =
+            decode_prefixed(&hybrid_id, HYBRID_SEALER_ID_PREFIX, "hybrid sealer ID").unwrap();
+        bytes[..32].fill(0);
+        let low_order_id = encode_prefixed(HYBRID_SEALER_ID_PREFIX, &bytes);
+        assert!(matches!(
+            seal_hybrid_internal(b"hello", &sender_secret, &low_order_id, nonce_material),
+            Err(CryptoError::InvalidPublicKey(_))
+        ));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::error::CryptoError;
+use argon2::{Algorithm, Argon2, AssociatedData, ParamsBuilder, Version};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Length of the secret seed derived from a passphrase.
+pub const SECRET_SEED_LENGTH: usize = 32;
+
+/// Minimum salt length, as recommended by RFC 9106.
+pub const MIN_SALT_LENGTH: usize = 16;
+
+/// Default Argon2id memory cost in KiB (64 MiB).
+pub const DEFAULT_KDF_MEMORY_KIB: u32 = 64 * 1024;
+
+/// Default Argon2id number of passes over memory.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 3;
+
+/// Default Argon2id degree of parallelism.
+/// WebAssembly runs single-threaded, so extra lanes only add work without adding hardness.
+pub const DEFAULT_KDF_PARALLELISM: u32 = 1;
+
+/// Argon2id cost parameters for `derive_secret_seed_from_passphrase`.
+/// The defaults follow RFC 9106's second recommended option (64 MiB, 3 passes)
+/// with a single lane, which stays within mobile browser memory limits.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KdfParams {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: DEFAULT_KDF_MEMORY_KIB,
+            iterations: DEFAULT_KDF_ITERATIONS,
+            parallelism: DEFAULT_KDF_PARALLELISM,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl KdfParams {
+    /// Create parameters with the browser-safe defaults.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Memory cost in KiB.
+    #[wasm_bindgen(getter)]
+    pub fn memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_memory_kib(&mut self, memory_kib: u32) {
+        self.memory_kib = memory_kib;
+    }
+
+    /// Number of passes over memory.
+    #[wasm_bindgen(getter)]
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_iterations(&mut self, iterations: u32) {
+        self.iterations = iterations;
+    }
+
+    /// Degree of parallelism (number of lanes).
+    #[wasm_bindgen(getter)]
+    pub fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_parallelism(&mut self, parallelism: u32) {
+        self.parallelism = parallelism;
+    }
+}
+
+/// Run Argon2id (version 0x13) with an optional secret key and associated data.
+fn argon2id(
+    passphrase: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    associated_data: &[u8],
+    params: &KdfParams,
+    output: &mut [u8],
+) -> Result<(), CryptoError> {
+    let associated_data = AssociatedData::new(associated_data)
+        .map_err(|e| CryptoError::InvalidKdfParams(e.to_string()))?;
+    let argon2_params = ParamsBuilder::new()
+        .m_cost(params.memory_kib)
+        .t_cost(params.iterations)
+        .p_cost(params.parallelism)
+        .data(associated_data)
+        .output_len(output.len())
+        .build()
+        .map_err(|e| CryptoError::InvalidKdfParams(e.to_string()))?;
+    let argon2 =
+        Argon2::new_with_secret(secret, Algorithm::Argon2id, Version::V0x13, argon2_params)
+            .map_err(|e| CryptoError::InvalidKdfParams(e.to_string()))?;
+    argon2
+        .hash_password_into(passphrase, salt, output)
+        .map_err(|e| CryptoError::InvalidKdfParams(e.to_string()))
+}
+
+/// Internal function to derive a secret seed from a passphrase with Argon2id.
+/// - `passphrase`: The user's passphrase as raw bytes
+/// - `salt`: A random salt of at least 16 bytes, stored alongside whatever the seed protects
+/// - `params`: Argon2id memory, time and parallelism costs
+///
+/// Returns a 32-byte secret seed or CryptoError::InvalidKdfParams if the salt or costs are invalid.
+pub fn derive_secret_seed_from_passphrase_internal(
+    passphrase: &[u8],
+    salt: &[u8],
+    params: &KdfParams,
+) -> Result<Zeroizing<[u8; SECRET_SEED_LENGTH]>, CryptoError> {
+    if salt.len() < MIN_SALT_LENGTH {
+        return Err(CryptoError::InvalidKdfParams(format!(
+            "salt must be at least {MIN_SALT_LENGTH} bytes, got {}",
+            salt.len()
+        )));
+    }
+    let mut seed = Zeroizing::new([0u8; SECRET_SEED_LENGTH]);
+    argon2id(passphrase, salt, &[], &[], params, &mut *seed)?;
+    Ok(seed)
+}
+
+/// WASM-exposed function to derive a secret seed from a passphrase with Argon2id.
+/// - `passphrase`: The user's passphrase
+/// - `salt`: A random salt of at least 16 bytes
+/// - `params`: Argon2id costs, or the browser-safe defaults if omitted
+///
+/// Returns a 32-byte secret seed or throws JsError if the salt or costs are invalid.
+#[wasm_bindgen]
+pub fn derive_secret_seed_from_passphrase(
+    passphrase: &str,
+    salt: &[u8],
+    params: Option<KdfParams>,
+) -> Result<Box<[u8]>, JsError> {
+    let seed = derive_secret_seed_from_passphrase_internal(
+        passphrase.as_bytes(),
+        salt,
+        &params.unwrap_or_default(),
+    )?;
+    Ok(seed.to_vec().into())
+}
+
+/// Small Argon2id costs so tests that derive keys stay fast.
+#[cfg(test)]
+pub(crate) fn test_params() -> KdfParams {
+    let mut params = KdfParams::new();
+    params.set_memory_kib(64);
+    params.set_iterations(1);
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_argon2id_rfc9106_vector() {
+        // RFC 9106, section 5.3
+        let mut params = KdfParams::new();
+        params.set_memory_kib(32);
+        params.set_iterations(3);
+        params.set_parallelism(4);
+
+        let mut tag = [0u8; 32];
+        argon2id(
+            &[1u8; 32], &[2u8; 16], &[3u8; 8], &[4u8; 12], &params, &mut tag,
+        )
+        .unwrap();
+        assert_eq!(
+            tag,
+            [
+                0x0d, 0x64, 0x0d, 0xf5, 0x8d, 0x78, 0x76, 0x6c, 0x08, 0xc0, 0x37, 0xa3, 0x4a, 0x8b,
+                0x53, 0xc9, 0xd0, 0x1e, 0xf0, 0x45, 0x2d, 0x75, 0xb6, 0x5e, 0xb5, 0x25, 0x20, 0xe9,
+                0x6b, 0x01, 0xe6, 0x59,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derive_secret_seed_from_passphrase() {
+        let params = test_params();
+        let salt = [7u8; 16];
+
+        let seed =
+            derive_secret_seed_from_passphrase_internal(b"correct horse", &salt, &params).unwrap();
+        assert_eq!(seed.len(), SECRET_SEED_LENGTH);
+
+        // Deterministic in the passphrase, salt and params
+        let mut expected = [0u8; SECRET_SEED_LENGTH];
+        argon2id(b"correct horse", &salt, &[], &[], &params, &mut expected).unwrap();
+        assert_eq!(*seed, expected);
+
+        // Any change gives an unrelated seed
+        let other_passphrase =
+            derive_secret_seed_from_passphrase_internal(b"correct horsf", &salt, &params).unwrap();
+        let other_salt =
+            derive_secret_seed_from_passphrase_internal(b"correct horse", &[8u8; 16], &params)
+                .unwrap();
+        let mut slower = params;
+        slower.set_iterations(2);
+        let other_params =
+            derive_secret_seed_from_passphrase_internal(b"correct horse", &salt, &slower).unwrap();
+        assert_ne!(*seed, *other_passphrase);
+        assert_ne!(*seed, *other_salt);
+        assert_ne!(*seed, *other_params);
+    }
+
+    #[test]
+    fn test_invalid_kdf_params() {
+        let params = test_params();
+
+        // Salt too short
+        assert!(matches!(
+            derive_secret_seed_from_passphrase_internal(b"passphrase", &[0u8; 15], &params),
+            Err(CryptoError::InvalidKdfParams(_))
+        ));
+
+        // Costs below Argon2's minimums
+        let mut no_iterations = params;
+        no_iterations.set_iterations(0);
+        let mut no_lanes = params;
+        no_lanes.set_parallelism(0);
+        let mut too_little_memory = params;
+        too_little_memory.set_memory_kib(4);
+        for params in [no_iterations, no_lanes, too_little_memory] {
+            assert!(matches!(
+                derive_secret_seed_from_passphrase_internal(b"passphrase", &[0u8; 16], &params),
+                Err(CryptoError::InvalidKdfParams(_))
+            ));
+        }
+
+        // Browser-safe defaults
+        let defaults = KdfParams::new();
+        assert_eq!(defaults.memory_kib(), 64 * 1024);
+        assert_eq!(defaults.iterations(), 3);
+        assert_eq!(defaults.parallelism(), 1);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::CryptoError;
+use ml_dsa::{
+    EncodedSignature, EncodedVerifyingKey, KeyGen, KeyPair, MlDsa65, Signature, VerifyingKey, B32,
+};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Length of an ML-DSA signing key seed (FIPS 204 ξ).
+pub const ML_DSA_SEED_LENGTH: usize = 32;
+
+/// Length of an ML-DSA-65 verifying key.
+pub const ML_DSA_VERIFYING_KEY_LENGTH: usize = 1952;
+
+/// Length of an ML-DSA-65 signature.
+pub const ML_DSA_SIGNATURE_LENGTH: usize = 3309;
+
+/// Generate a new ML-DSA-65 signing key using secure random number generation.
+/// Returns the 32-byte seed the key pair is expanded from, which is all that needs to be stored.
+#[wasm_bindgen]
+pub fn new_ml_dsa_signing_key() -> Box<[u8]> {
+    let mut seed = [0u8; ML_DSA_SEED_LENGTH];
+    OsRng.fill_bytes(&mut seed);
+    seed.into()
+}
+
+/// Expand a 32-byte seed into an ML-DSA-65 key pair (FIPS 204 ML-DSA.KeyGen_internal).
+fn ml_dsa_key_pair(signing_key: &[u8]) -> Result<KeyPair<MlDsa65>, CryptoError> {
+    let seed = Zeroizing::new(
+        B32::try_from(signing_key)
+            .map_err(|_| CryptoError::InvalidKeyLength(ML_DSA_SEED_LENGTH, signing_key.len()))?,
+    );
+    Ok(MlDsa65::key_gen_internal(&seed))
+}
+
+/// Internal function to derive an ML-DSA-65 verifying key from a signing key.
+/// Takes a 32-byte signing key seed and returns 1952 bytes of verifying key material.
+/// Returns CryptoError if the key length is invalid.
+pub(crate) fn ml_dsa_verifying_key_internal(signing_key: &[u8]) -> Result<Box<[u8]>, CryptoError> {
+    let key_pair = ml_dsa_key_pair(signing_key)?;
+    Ok(key_pair.verifying_key().encode().to_vec().into())
+}
+
+/// WASM-exposed function to derive an ML-DSA-65 verifying key from a signing key.
+/// - `signing_key`: 32-byte signing key seed
+///
+/// Returns 1952 bytes of verifying key material or throws JsError if key is invalid.
+#[wasm_bindgen]
+pub fn ml_dsa_verifying_key(signing_key: &[u8]) -> Result<Box<[u8]>, JsError> {
+    Ok(ml_dsa_verifying_key_internal(signing_key)?)
+}
+
+/// Internal function to sign a message using ML-DSA-65.
+/// Uses the hedged (randomized) variant of ML-DSA.Sign with an empty context string.
+/// Takes a 32-byte signing key seed and arbitrary message bytes.
+/// Returns 3309 bytes of signature material or CryptoError if key is invalid.
+pub(crate) fn ml_dsa_sign_internal(
+    signing_key: &[u8],
+    message: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    let key_pair = ml_dsa_key_pair(signing_key)?;
+    let signature = key_pair
+        .signing_key()
+        .sign_randomized(message, &[], &mut OsRng)
+        .map_err(|_| CryptoError::CipherError)?;
+    Ok(signature.encode().to_vec().into())
+}
+
+/// WASM-exposed function to sign a message using ML-DSA-65.
+/// - `signing_key`: 32-byte signing key seed
+/// - `message`: Raw bytes to sign
+///
+/// Returns 3309 bytes of signature material or throws JsError if signing fails.
+#[wasm_bindgen]
+pub fn ml_dsa_sign(signing_key: &[u8], message: &[u8]) -> Result<Box<[u8]>, JsError> {
+    Ok(ml_dsa_sign_internal(signing_key, message)?)
+}
+
+/// Internal function to verify an ML-DSA-65 signature.
+/// - `verifying_key`: 1952 bytes of verifying key material
+/// - `message`: Raw bytes that were signed
+/// - `signature`: 3309 bytes of signature material
+///
+/// Returns true if signature is valid, false otherwise, or CryptoError if lengths are invalid.
+pub(crate) fn ml_dsa_verify_internal(
+    verifying_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, CryptoError> {
+    let key_bytes = EncodedVerifyingKey::<MlDsa65>::try_from(verifying_key).map_err(|_| {
+        CryptoError::InvalidKeyLength(ML_DSA_VERIFYING_KEY_LENGTH, verifying_key.len())
+    })?;
+    let verifying_key = VerifyingKey::<MlDsa65>::decode(&key_bytes);
+
+    let sig_bytes = EncodedSignature::<MlDsa65>::try_from(signature)
+        .map_err(|_| CryptoError::InvalidSignatureLength)?;
+    // A malformed signature encoding can never verify
+    let Some(signature) = Signature::<MlDsa65>::decode(&sig_bytes) else {
+        return Ok(false);
+    };
+
+    Ok(verifying_key.verify_with_context(message, &[], &signature))
+}
+
+/// WASM-exposed function to verify an ML-DSA-65 signature.
+/// - `verifying_key`: 1952 bytes of verifying key material
+/// - `message`: Raw bytes that were signed
+/// - `signature`: 3309 bytes of signature material
+///
+/// Returns true if signature is valid, false otherwise, or throws JsError if verification fails.
+#[wasm_bindgen]
+pub fn ml_dsa_verify(
+    verifying_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, JsError> {
+    Ok(ml_dsa_verify_internal(verifying_key, message, signature)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ml_dsa::{EncodedSigningKey, SigningKey};
+    use serde_json::Value;
+
+    fn kat() -> Value {
+        serde_json::from_str(include_str!("testdata/ml_dsa_65_kat.json")).unwrap()
+    }
+
+    #[test]
+    fn test_ml_dsa_sign_and_verify() {
+        let signing_key = new_ml_dsa_signing_key();
+        assert_eq!(signing_key.len(), ML_DSA_SEED_LENGTH);
+        let verifying_key = ml_dsa_verifying_key_internal(&signing_key).unwrap();
+        assert_eq!(verifying_key.len(), ML_DSA_VERIFYING_KEY_LENGTH);
+
+        let message = b"hello world";
+        let signature = ml_dsa_sign_internal(&signing_key, message).unwrap();
+        assert_eq!(signature.len(), ML_DSA_SIGNATURE_LENGTH);
+        assert!(ml_dsa_verify_internal(&verifying_key, message, &signature).unwrap());
+        assert!(!ml_dsa_verify_internal(&verifying_key, b"goodbye", &signature).unwrap());
+
+        // Hedged signing gives a fresh signature each time, and both verify
+        let signature2 = ml_dsa_sign_internal(&signing_key, message).unwrap();
+        assert_ne!(signature, signature2);
+        assert!(ml_dsa_verify_internal(&verifying_key, message, &signature2).unwrap());
+
+        // Another key's signature doesn't verify
+        let other_key = new_ml_dsa_signing_key();
+        let other_signature = ml_dsa_sign_internal(&other_key, message).unwrap();
+        assert!(!ml_dsa_verify_internal(&verifying_key, message, &other_signature).unwrap());
+
+        // Invalid lengths
+        assert!(matches!(
+            ml_dsa_sign_internal(&[0u8; 31], message),
+            Err(CryptoError::InvalidKeyLength(32, 31))
+        ));
+        assert!(matches!(
+            ml_dsa_verify_internal(&verifying_key[..32], message, &signature),
+            Err(CryptoError::InvalidKeyLength(1952, 32))
+        ));
+        assert!(matches!(
+            ml_dsa_verify_internal(&verifying_key, message, &signature[..64]),
+            Err(CryptoError::InvalidSignatureLength)
+        ));
+    }
+
+    #[test]
+    fn test_ml_dsa_key_gen_kat() {
+        for case in kat()["keyGen"].as_array().unwrap() {
+            let seed = hex::decode(case["seed"].as_str().unwrap()).unwrap();
+            let expected_pk = hex::decode(case["pk"].as_str().unwrap()).unwrap();
+            let expected_sk = hex::decode(case["sk"].as_str().unwrap()).unwrap();
+
+            assert_eq!(
+                ml_dsa_verifying_key_internal(&seed).unwrap().to_vec(),
+                expected_pk
+            );
+            let key_pair = ml_dsa_key_pair(&seed).unwrap();
+            assert_eq!(key_pair.signing_key().encode().to_vec(), expected_sk);
+        }
+    }
+
+    #[test]
+    fn test_ml_dsa_sig_gen_kat() {
+        // Deterministic ML-DSA.Sign_internal (rnd = 0) over the raw message representative
+        for case in kat()["sigGen"].as_array().unwrap() {
+            let sk = hex::decode(case["sk"].as_str().unwrap()).unwrap();
+            let message = hex::decode(case["message"].as_str().unwrap()).unwrap();
+            let expected = hex::decode(case["signature"].as_str().unwrap()).unwrap();
+
+            let sk = EncodedSigningKey::<MlDsa65>::try_from(sk.as_slice()).unwrap();
+            let signing_key = SigningKey::<MlDsa65>::decode(&sk);
+            let signature = signing_key.sign_internal(&[&message], &B32::default());
+            assert_eq!(signature.encode().to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn test_ml_dsa_sig_ver_kat() {
+        let kat = kat();
+        let pk = hex::decode(kat["sigVer"]["pk"].as_str().unwrap()).unwrap();
+        let pk = EncodedVerifyingKey::<MlDsa65>::try_from(pk.as_slice()).unwrap();
+        let verifying_key = VerifyingKey::<MlDsa65>::decode(&pk);
+
+        for case in kat["sigVer"]["tests"].as_array().unwrap() {
+            let message = hex::decode(case["message"].as_str().unwrap()).unwrap();
+            let signature = hex::decode(case["signature"].as_str().unwrap()).unwrap();
+            let expected = case["testPassed"].as_bool().unwrap();
+
+            let signature = EncodedSignature::<MlDsa65>::try_from(signature.as_slice()).unwrap();
+            let valid = Signature::<MlDsa65>::decode(&signature)
+                .is_some_and(|signature| verifying_key.verify_internal(&[&message], &signature));
+            assert_eq!(valid, expected, "tcId {}", case["tcId"]);
+        }
+    }
+}
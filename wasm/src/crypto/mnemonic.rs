@@ -0,0 +1,156 @@
+use crate::crypto::kdf::SECRET_SEED_LENGTH;
+use crate::error::CryptoError;
+use bip39::{Language, Mnemonic};
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Number of BIP39 words encoding a 32-byte secret seed (256 bits of entropy plus an 8-bit checksum).
+pub const MNEMONIC_WORD_COUNT: usize = 24;
+
+/// Internal function to encode a secret seed as a BIP39 mnemonic.
+/// The seed is used directly as the BIP39 entropy, so the mnemonic round-trips to the same seed.
+/// Returns 24 space-separated English words or CryptoError if the seed is not 32 bytes.
+pub fn secret_seed_to_mnemonic_internal(secret_seed: &[u8]) -> Result<String, CryptoError> {
+    if secret_seed.len() != SECRET_SEED_LENGTH {
+        return Err(CryptoError::InvalidLength(
+            "secret seed",
+            SECRET_SEED_LENGTH,
+            secret_seed.len(),
+        ));
+    }
+    // 256 bits is always a valid BIP39 entropy length
+    let mnemonic = Mnemonic::from_entropy_in(Language::English, secret_seed).unwrap();
+    Ok(mnemonic.to_string())
+}
+
+/// Internal function to decode a BIP39 mnemonic back into a secret seed.
+/// Words are matched case-insensitively and may be separated by any whitespace.
+/// Returns the 32-byte secret seed, or CryptoError::InvalidLength if there aren't 24 words,
+/// CryptoError::UnknownMnemonicWord with the 1-based position of a word not in the English list,
+/// or CryptoError::InvalidMnemonicChecksum if the words don't form a valid mnemonic.
+pub fn mnemonic_to_secret_seed_internal(
+    mnemonic: &str,
+) -> Result<Zeroizing<[u8; SECRET_SEED_LENGTH]>, CryptoError> {
+    let mnemonic = Zeroizing::new(mnemonic.to_lowercase());
+    let word_count = mnemonic.split_whitespace().count();
+    if word_count != MNEMONIC_WORD_COUNT {
+        return Err(CryptoError::InvalidLength(
+            "mnemonic word",
+            MNEMONIC_WORD_COUNT,
+            word_count,
+        ));
+    }
+
+    let mnemonic =
+        Mnemonic::parse_in(Language::English, mnemonic.as_str()).map_err(|e| match e {
+            bip39::Error::UnknownWord(index) => CryptoError::UnknownMnemonicWord(index + 1),
+            _ => CryptoError::InvalidMnemonicChecksum,
+        })?;
+    let (entropy, len) = mnemonic.to_entropy_array();
+    let entropy = Zeroizing::new(entropy);
+
+    let mut secret_seed = Zeroizing::new([0u8; SECRET_SEED_LENGTH]);
+    secret_seed.copy_from_slice(&entropy[..len]);
+    Ok(secret_seed)
+}
+
+/// WASM-exposed function to encode a secret seed as a BIP39 mnemonic.
+/// - `secret_seed`: 32-byte secret seed
+///
+/// Returns 24 space-separated English words or throws JsError if the seed is invalid.
+#[wasm_bindgen]
+pub fn secret_seed_to_mnemonic(secret_seed: &[u8]) -> Result<String, JsError> {
+    Ok(secret_seed_to_mnemonic_internal(secret_seed)?)
+}
+
+/// WASM-exposed function to decode a BIP39 mnemonic into a secret seed.
+/// - `mnemonic`: 24 English words
+///
+/// Returns the 32-byte secret seed or throws JsError for unknown words or a bad checksum.
+#[wasm_bindgen]
+pub fn mnemonic_to_secret_seed(mnemonic: &str) -> Result<Box<[u8]>, JsError> {
+    Ok(mnemonic_to_secret_seed_internal(mnemonic)?.to_vec().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip39_vectors() {
+        // 256-bit vectors from the BIP39 reference test suite
+        let vectors: [([u8; 32], &str); 4] = [
+            (
+                [0x00; 32],
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+            ),
+            (
+                [0x7f; 32],
+                "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title",
+            ),
+            (
+                [0x80; 32],
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+            ),
+            (
+                [0xff; 32],
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            ),
+        ];
+        for (seed, words) in vectors {
+            assert_eq!(secret_seed_to_mnemonic_internal(&seed).unwrap(), words);
+            assert_eq!(*mnemonic_to_secret_seed_internal(words).unwrap(), seed);
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_roundtrip() {
+        let seed: [u8; 32] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        let mnemonic = secret_seed_to_mnemonic_internal(&seed).unwrap();
+        assert_eq!(mnemonic.split(' ').count(), MNEMONIC_WORD_COUNT);
+        assert_eq!(*mnemonic_to_secret_seed_internal(&mnemonic).unwrap(), seed);
+
+        // Case and extra whitespace don't matter
+        let sloppy = format!("  {}\n", mnemonic.to_uppercase().replace(' ', "\t "));
+        assert_eq!(*mnemonic_to_secret_seed_internal(&sloppy).unwrap(), seed);
+    }
+
+    #[test]
+    fn test_invalid_mnemonics() {
+        let words = secret_seed_to_mnemonic_internal(&[0x7f; 32]).unwrap();
+        let mut words: Vec<&str> = words.split(' ').collect();
+
+        // Unknown word, reported by position
+        words[4] = "wavy";
+        assert!(matches!(
+            mnemonic_to_secret_seed_internal(&words.join(" ")),
+            Err(CryptoError::UnknownMnemonicWord(5))
+        ));
+
+        // Valid words with the wrong checksum
+        words[4] = "wave";
+        words[23] = "zoo";
+        assert!(matches!(
+            mnemonic_to_secret_seed_internal(&words.join(" ")),
+            Err(CryptoError::InvalidMnemonicChecksum)
+        ));
+
+        // Wrong word count, including shorter valid BIP39 mnemonics
+        assert!(matches!(
+            mnemonic_to_secret_seed_internal(&words[..23].join(" ")),
+            Err(CryptoError::InvalidLength("mnemonic word", 24, 23))
+        ));
+        assert!(matches!(
+            mnemonic_to_secret_seed_internal(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+            ),
+            Err(CryptoError::InvalidLength("mnemonic word", 24, 12))
+        ));
+
+        // Seeds must be 32 bytes
+        assert!(matches!(
+            secret_seed_to_mnemonic_internal(&[0u8; 16]),
+            Err(CryptoError::InvalidLength("secret seed", 32, 16))
+        ));
+    }
+}
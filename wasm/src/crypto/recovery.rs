@@ -0,0 +1,357 @@
+use crate::error::CryptoError;
+use crate::prefix::{decode_prefixed, encode_prefixed, SHARE_PREFIX};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// BLAKE3 derive_key context for the secret checksum split along with the secret.
+const SECRET_CHECKSUM_CONTEXT: &str = "jazz-crypto-rs 2025 recovery secret checksum";
+
+/// Current version of the share encoding.
+const SHARE_VERSION: u8 = 1;
+
+/// Length of the share header: version, threshold and index.
+const SHARE_HEADER_LENGTH: usize = 3;
+
+/// Length of the per-share BLAKE3 checksum catching typos in a single share.
+const SHARE_CHECKSUM_LENGTH: usize = 4;
+
+/// Length of the secret checksum appended to the secret before splitting.
+const SECRET_CHECKSUM_LENGTH: usize = 32;
+
+/// Multiply two elements of GF(256) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+/// Runs in constant time, since share bytes are secret.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Invert a non-zero element of GF(256) as a^254.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Internal function to split a secret into Shamir shares over GF(256).
+/// - `secret`: The secret to split, such as a 32-byte secret seed
+/// - `threshold`: Number of shares needed to recover the secret, at least 2
+/// - `share_count`: Number of shares to create, between `threshold` and 255
+///
+/// Returns `share_count` shares of the form "share_z..." or CryptoError::InvalidShareParams.
+///
+/// A BLAKE3 checksum of the secret is split along with it, so combining shares from
+/// different splits, or a tampered share, is detected instead of yielding a wrong secret.
+/// Each share also ends in a short checksum of its own bytes to catch transcription errors.
+pub fn split_secret_internal(
+    secret: &[u8],
+    threshold: u8,
+    share_count: u8,
+) -> Result<Vec<String>, CryptoError> {
+    if secret.is_empty() {
+        return Err(CryptoError::InvalidShareParams("secret is empty"));
+    }
+    if threshold < 2 {
+        return Err(CryptoError::InvalidShareParams(
+            "threshold must be at least 2",
+        ));
+    }
+    if share_count < threshold {
+        return Err(CryptoError::InvalidShareParams(
+            "share count must be at least the threshold",
+        ));
+    }
+
+    let mut payload = Zeroizing::new(secret.to_vec());
+    payload.extend_from_slice(&blake3::derive_key(SECRET_CHECKSUM_CONTEXT, secret));
+
+    // coefficients[d * len + j] is the degree d + 1 coefficient for payload byte j
+    let mut coefficients = Zeroizing::new(vec![0u8; (threshold as usize - 1) * payload.len()]);
+    OsRng.fill_bytes(&mut coefficients);
+
+    let shares = (1..=share_count)
+        .map(|x| {
+            let mut share = Zeroizing::new(Vec::with_capacity(
+                SHARE_HEADER_LENGTH + payload.len() + SHARE_CHECKSUM_LENGTH,
+            ));
+            share.extend_from_slice(&[SHARE_VERSION, threshold, x]);
+            for (j, &constant) in payload.iter().enumerate() {
+                // Horner's rule, from the highest degree down to the secret byte
+                let y = coefficients
+                    .chunks_exact(payload.len())
+                    .rev()
+                    .fold(0u8, |y, coefficients| gf_mul(y, x) ^ coefficients[j]);
+                share.push(gf_mul(y, x) ^ constant);
+            }
+            let checksum = blake3::hash(&share);
+            share.extend_from_slice(&checksum.as_bytes()[..SHARE_CHECKSUM_LENGTH]);
+            encode_prefixed(SHARE_PREFIX, &share)
+        })
+        .collect();
+    Ok(shares)
+}
+
+/// Decode a share into its threshold, index and share bytes.
+fn decode_share(share: &str, position: usize) -> Result<(u8, u8, Zeroizing<Vec<u8>>), CryptoError> {
+    let bytes = Zeroizing::new(
+        decode_prefixed(share, SHARE_PREFIX, "share")
+            .map_err(|_| CryptoError::InvalidShare(position, "not a share_z string"))?,
+    );
+    if bytes.len() < SHARE_HEADER_LENGTH + SECRET_CHECKSUM_LENGTH + 1 + SHARE_CHECKSUM_LENGTH {
+        return Err(CryptoError::InvalidShare(position, "truncated"));
+    }
+    let (body, checksum) = bytes.split_at(bytes.len() - SHARE_CHECKSUM_LENGTH);
+    if blake3::hash(body).as_bytes()[..SHARE_CHECKSUM_LENGTH] != *checksum {
+        return Err(CryptoError::InvalidShare(position, "checksum mismatch"));
+    }
+    if body[0] != SHARE_VERSION {
+        return Err(CryptoError::InvalidShare(position, "unsupported version"));
+    }
+    let (threshold, index) = (body[1], body[2]);
+    if threshold < 2 || index == 0 {
+        return Err(CryptoError::InvalidShare(position, "invalid header"));
+    }
+    Ok((
+        threshold,
+        index,
+        Zeroizing::new(body[SHARE_HEADER_LENGTH..].to_vec()),
+    ))
+}
+
+/// Internal function to recover a secret from Shamir shares made by `split_secret_internal`.
+/// - `shares`: At least `threshold` distinct shares of the same split, in any order
+///
+/// Returns the secret, CryptoError::InvalidShare with the 1-based position of a malformed share,
+/// CryptoError::NotEnoughShares, or CryptoError::ShareMismatch if the shares don't belong together.
+///
+/// Every share given is used, so a single tampered share among more than `threshold` is still caught.
+pub fn combine_shares_internal(shares: &[&str]) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+    let decoded = shares
+        .iter()
+        .enumerate()
+        .map(|(i, share)| decode_share(share, i + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let Some((threshold, _, first)) = decoded.first() else {
+        return Err(CryptoError::NotEnoughShares(2, 0));
+    };
+    for (i, (other_threshold, index, ys)) in decoded.iter().enumerate() {
+        if other_threshold != threshold || ys.len() != first.len() {
+            return Err(CryptoError::InvalidShare(
+                i + 1,
+                "belongs to a different split",
+            ));
+        }
+        if decoded[..i].iter().any(|(_, seen, _)| seen == index) {
+            return Err(CryptoError::InvalidShare(i + 1, "duplicate share index"));
+        }
+    }
+    if decoded.len() < *threshold as usize {
+        return Err(CryptoError::NotEnoughShares(
+            *threshold as usize,
+            decoded.len(),
+        ));
+    }
+
+    // Lagrange basis polynomials evaluated at x = 0; subtraction in GF(256) is XOR
+    let basis: Vec<u8> = decoded
+        .iter()
+        .map(|(_, xi, _)| {
+            decoded
+                .iter()
+                .filter(|(_, xm, _)| xm != xi)
+                .fold(1u8, |acc, (_, xm, _)| {
+                    gf_mul(acc, gf_mul(*xm, gf_inv(xm ^ xi)))
+                })
+        })
+        .collect();
+    let mut payload = Zeroizing::new(vec![0u8; first.len()]);
+    for ((_, _, ys), &l) in decoded.iter().zip(&basis) {
+        for (byte, &y) in payload.iter_mut().zip(ys.iter()) {
+            *byte ^= gf_mul(y, l);
+        }
+    }
+
+    let secret_length = payload.len() - SECRET_CHECKSUM_LENGTH;
+    let checksum: [u8; SECRET_CHECKSUM_LENGTH] = payload[secret_length..].try_into().unwrap();
+    // blake3::Hash equality is constant-time
+    if blake3::Hash::from_bytes(blake3::derive_key(
+        SECRET_CHECKSUM_CONTEXT,
+        &payload[..secret_length],
+    )) != blake3::Hash::from_bytes(checksum)
+    {
+        return Err(CryptoError::ShareMismatch);
+    }
+    payload.truncate(secret_length);
+    Ok(payload)
+}
+
+/// WASM-exposed function to split a secret into Shamir shares for k-of-n recovery.
+/// - `secret`: The secret to split, such as a 32-byte secret seed
+/// - `threshold`: Number of shares needed to recover the secret, at least 2
+/// - `share_count`: Number of shares to create, between `threshold` and 255
+///
+/// Returns an array of "share_z..." strings or throws JsError if the parameters are invalid.
+#[wasm_bindgen]
+pub fn split_secret(secret: &[u8], threshold: u8, share_count: u8) -> Result<Vec<String>, JsError> {
+    Ok(split_secret_internal(secret, threshold, share_count)?)
+}
+
+/// WASM-exposed function to recover a secret from Shamir shares.
+/// - `shares`: At least `threshold` distinct "share_z..." strings from the same split
+///
+/// Returns the secret or throws JsError if shares are malformed, missing or don't belong together.
+#[wasm_bindgen]
+pub fn combine_shares(shares: Vec<String>) -> Result<Box<[u8]>, JsError> {
+    let shares: Vec<&str> = shares.iter().map(String::as_str).collect();
+    Ok(combine_shares_internal(&shares)?.to_vec().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combine(shares: &[String]) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+        combine_shares_internal(&shares.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_gf256_arithmetic() {
+        // FIPS 197, section 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+            assert_eq!(gf_mul(a, 1), a);
+            assert_eq!(gf_mul(a, 0), 0);
+        }
+    }
+
+    #[test]
+    fn test_split_and_combine() {
+        let secret: [u8; 32] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        let shares = split_secret_internal(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.starts_with("share_z")));
+
+        // Any 3 of the 5 shares, in any order, recover the secret
+        for a in 0..5 {
+            for b in 0..5 {
+                for c in 0..5 {
+                    if a == b || b == c || a == c {
+                        continue;
+                    }
+                    let subset = [shares[a].clone(), shares[b].clone(), shares[c].clone()];
+                    assert_eq!(*combine(&subset).unwrap(), secret);
+                }
+            }
+        }
+        assert_eq!(*combine(&shares).unwrap(), secret);
+
+        // Secrets of any length, up to 255 shares
+        let shares = split_secret_internal(b"x", 2, 255).unwrap();
+        assert_eq!(*combine(&shares[253..]).unwrap(), b"x");
+
+        // Fresh randomness on every split
+        assert_ne!(
+            split_secret_internal(&secret, 2, 2).unwrap(),
+            split_secret_internal(&secret, 2, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_combine_rejects_bad_shares() {
+        let secret = [42u8; 32];
+        let shares = split_secret_internal(&secret, 3, 5).unwrap();
+
+        assert!(matches!(
+            combine(&shares[..2]),
+            Err(CryptoError::NotEnoughShares(3, 2))
+        ));
+        assert!(matches!(
+            combine(&[]),
+            Err(CryptoError::NotEnoughShares(2, 0))
+        ));
+        assert!(matches!(
+            combine(&[shares[0].clone(), shares[1].clone(), shares[0].clone()]),
+            Err(CryptoError::InvalidShare(3, "duplicate share index"))
+        ));
+
+        // A typo in one share is pinned to that share
+        let mut bytes = decode_prefixed(&shares[1], SHARE_PREFIX, "share").unwrap();
+        bytes[10] ^= 1;
+        let typo = encode_prefixed(SHARE_PREFIX, &bytes);
+        assert!(matches!(
+            combine(&[shares[0].clone(), typo, shares[2].clone()]),
+            Err(CryptoError::InvalidShare(2, "checksum mismatch"))
+        ));
+        assert!(matches!(
+            combine(&[shares[0].clone(), "sealer_z1111".into(), shares[2].clone()]),
+            Err(CryptoError::InvalidShare(2, "not a share_z string"))
+        ));
+
+        // A deliberately altered share with a valid share checksum is caught when combining
+        let mut bytes = decode_prefixed(&shares[1], SHARE_PREFIX, "share").unwrap();
+        bytes[10] ^= 1;
+        let body_len = bytes.len() - SHARE_CHECKSUM_LENGTH;
+        let checksum = blake3::hash(&bytes[..body_len]);
+        bytes[body_len..].copy_from_slice(&checksum.as_bytes()[..SHARE_CHECKSUM_LENGTH]);
+        let forged = encode_prefixed(SHARE_PREFIX, &bytes);
+        assert!(matches!(
+            combine(&[shares[0].clone(), forged.clone(), shares[2].clone()]),
+            Err(CryptoError::ShareMismatch)
+        ));
+        assert!(matches!(
+            combine(&[
+                shares[0].clone(),
+                forged,
+                shares[2].clone(),
+                shares[3].clone()
+            ]),
+            Err(CryptoError::ShareMismatch)
+        ));
+
+        // Shares from two splits of the same secret don't mix
+        let other = split_secret_internal(&secret, 3, 5).unwrap();
+        assert!(matches!(
+            combine(&[shares[0].clone(), shares[1].clone(), other[2].clone()]),
+            Err(CryptoError::ShareMismatch)
+        ));
+        let other_threshold = split_secret_internal(&secret, 2, 5).unwrap();
+        assert!(matches!(
+            combine(&[
+                shares[0].clone(),
+                shares[1].clone(),
+                other_threshold[2].clone()
+            ]),
+            Err(CryptoError::InvalidShare(3, "belongs to a different split"))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_share_params() {
+        for (secret, threshold, share_count) in
+            [(&[][..], 2, 3), (&[1u8][..], 1, 3), (&[1u8][..], 4, 3)]
+        {
+            assert!(matches!(
+                split_secret_internal(secret, threshold, share_count),
+                Err(CryptoError::InvalidShareParams(_))
+            ));
+        }
+    }
+}
@@ -1,15 +1,23 @@
-use crate::crypto::x25519::x25519_diffie_hellman_internal;
-use crate::crypto::xsalsa20::{decrypt_xsalsa20_poly1305, encrypt_xsalsa20_poly1305};
+use crate::crypto::agent::parse_sealer_id_internal;
+use crate::crypto::envelope::{decode_envelope_with_options, encode_envelope, EnvelopeOptions};
+use crate::crypto::x25519::{x25519_diffie_hellman_internal, x25519_public_key_internal};
+use crate::crypto::xsalsa20::{
+    decrypt_xsalsa20_poly1305, decrypt_xsalsa20_poly1305_committing_internal,
+    encrypt_xsalsa20_poly1305, encrypt_xsalsa20_poly1305_committing_internal,
+};
 use crate::error::CryptoError;
 use crate::hash::blake3::generate_nonce;
+use crate::prefix::SEALER_SECRET_PREFIX;
 use bs58;
 use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
 
 /// Internal function to seal a message using X25519 + XSalsa20-Poly1305.
 /// - `message`: Raw bytes to seal
 /// - `sender_secret`: Base58-encoded sender's private key with "sealerSecret_z" prefix
 /// - `recipient_id`: Base58-encoded recipient's public key with "sealer_z" prefix
 /// - `nonce_material`: Raw bytes used to generate the nonce
+///
 /// Returns sealed bytes or CryptoError if key formats are invalid.
 ///
 /// The sealing process:
@@ -23,33 +31,11 @@ pub fn seal_internal(
     recipient_id: &str,
     nonce_material: &[u8],
 ) -> Result<Vec<u8>, CryptoError> {
-    // Decode the base58 sender secret (removing the "sealerSecret_z" prefix)
-    let sender_secret =
-        sender_secret
-            .strip_prefix("sealerSecret_z")
-            .ok_or(CryptoError::InvalidPrefix(
-                "sealer secret",
-                "sealerSecret_z",
-            ))?;
-    let sender_private_key = bs58::decode(sender_secret)
-        .into_vec()
-        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
-
-    // Decode the base58 recipient ID (removing the "sealer_z" prefix)
-    let recipient_id = recipient_id
-        .strip_prefix("sealer_z")
-        .ok_or(CryptoError::InvalidPrefix("sealer ID", "sealer_z"))?;
-    let recipient_public_key = bs58::decode(recipient_id)
-        .into_vec()
-        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
-
+    let shared_secret = sealing_shared_secret(sender_secret, recipient_id)?;
     let nonce = generate_nonce(nonce_material);
 
-    // Generate shared secret using X25519
-    let shared_secret = x25519_diffie_hellman_internal(&sender_private_key, &recipient_public_key)?;
-
     // Encrypt message using XSalsa20-Poly1305
-    Ok(encrypt_xsalsa20_poly1305(&shared_secret, &nonce, message)?.into())
+    Ok(encrypt_xsalsa20_poly1305(&*shared_secret, &nonce, message)?.into())
 }
 
 /// Internal function to unseal a message using X25519 + XSalsa20-Poly1305.
@@ -57,6 +43,7 @@ pub fn seal_internal(
 /// - `recipient_secret`: Base58-encoded recipient's private key with "sealerSecret_z" prefix
 /// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
 /// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+///
 /// Returns unsealed bytes or CryptoError if key formats are invalid or authentication fails.
 ///
 /// The unsealing process:
@@ -70,33 +57,115 @@ fn unseal_internal(
     sender_id: &str,
     nonce_material: &[u8],
 ) -> Result<Box<[u8]>, CryptoError> {
-    // Decode the base58 recipient secret (removing the "sealerSecret_z" prefix)
-    let recipient_secret =
-        recipient_secret
-            .strip_prefix("sealerSecret_z")
-            .ok_or(CryptoError::InvalidPrefix(
-                "sealer secret",
-                "sealerSecret_z",
-            ))?;
-    let recipient_private_key = bs58::decode(recipient_secret)
-        .into_vec()
-        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+    let shared_secret = sealing_shared_secret(recipient_secret, sender_id)?;
+    let nonce = generate_nonce(nonce_material);
 
-    // Decode the base58 sender ID (removing the "sealer_z" prefix)
-    let sender_id = sender_id
-        .strip_prefix("sealer_z")
-        .ok_or(CryptoError::InvalidPrefix("sealer ID", "sealer_z"))?;
-    let sender_public_key = bs58::decode(sender_id)
-        .into_vec()
-        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+    // Decrypt message using XSalsa20-Poly1305
+    decrypt_xsalsa20_poly1305(&*shared_secret, &nonce, sealed_message)
+}
+
+/// Internal function to seal a message with key-committing XSalsa20-Poly1305.
+/// Same as `seal_internal`, but the output starts with a BLAKE3 commitment to the shared key,
+/// so a sealed message can't be crafted to open validly for two different recipients.
+pub fn seal_committing_internal(
+    message: &[u8],
+    sender_secret: &str,
+    recipient_id: &str,
+    nonce_material: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let shared_secret = sealing_shared_secret(sender_secret, recipient_id)?;
+    let nonce = generate_nonce(nonce_material);
+    Ok(encrypt_xsalsa20_poly1305_committing_internal(&*shared_secret, &nonce, message)?.into())
+}
 
+/// Internal function to unseal a message sealed with `seal_committing_internal`.
+/// Returns unsealed bytes, or CryptoError::KeyCommitmentMismatch if it was sealed under a different key.
+pub fn unseal_committing_internal(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    let shared_secret = sealing_shared_secret(recipient_secret, sender_id)?;
     let nonce = generate_nonce(nonce_material);
+    decrypt_xsalsa20_poly1305_committing_internal(&*shared_secret, &nonce, sealed_message)
+}
+
+/// Internal function to seal a message wrapped in an envelope, e.g. to pad it.
+/// - `options`: Envelope options, recorded inside the sealed message
+///
+/// Returns sealed bytes or CryptoError if key formats are invalid.
+pub fn seal_enveloped_internal(
+    message: &[u8],
+    sender_secret: &str,
+    recipient_id: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Vec<u8>, CryptoError> {
+    seal_internal(
+        &encode_envelope(message, options),
+        sender_secret,
+        recipient_id,
+        nonce_material,
+    )
+}
+
+/// Internal function to unseal a message sealed with `seal_enveloped_internal`.
+/// Padding and compression are read from the envelope.
+/// Returns unsealed bytes or CryptoError if authentication fails or the envelope is invalid.
+pub fn unseal_enveloped_internal(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    unseal_enveloped_with_options_internal(
+        sealed_message,
+        recipient_secret,
+        sender_id,
+        nonce_material,
+        &EnvelopeOptions::default(),
+    )
+}
+
+/// Internal function to unseal an envelope like `unseal_enveloped_internal`,
+/// with `options` limiting decompression.
+/// Returns unsealed bytes or CryptoError if authentication fails or the envelope is invalid.
+pub fn unseal_enveloped_with_options_internal(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, CryptoError> {
+    let envelope = unseal_internal(sealed_message, recipient_secret, sender_id, nonce_material)?;
+    Ok(decode_envelope_with_options(&envelope, options)?.into())
+}
+
+/// Decode "sealerSecret_z" and "sealer_z" keys and compute their X25519 shared secret.
+fn sealing_shared_secret(
+    our_secret: &str,
+    their_id: &str,
+) -> Result<Zeroizing<[u8; 32]>, CryptoError> {
+    let our_private_key = decode_sealer_secret(our_secret)?;
+    let their_public_key = parse_sealer_id_internal(their_id)?;
 
     // Generate shared secret using X25519
-    let shared_secret = x25519_diffie_hellman_internal(&recipient_private_key, &sender_public_key)?;
+    x25519_diffie_hellman_internal(&our_private_key, &their_public_key).map(Zeroizing::new)
+}
 
-    // Decrypt message using XSalsa20-Poly1305
-    Ok(decrypt_xsalsa20_poly1305(&shared_secret, &nonce, sealed_message)?.into())
+/// Decode the base58 sealer secret (removing the "sealerSecret_z" prefix).
+pub(crate) fn decode_sealer_secret(secret: &str) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+    let secret = secret
+        .strip_prefix(SEALER_SECRET_PREFIX)
+        .ok_or(CryptoError::InvalidPrefix(
+            "sealer secret",
+            SEALER_SECRET_PREFIX,
+        ))?;
+    bs58::decode(secret)
+        .into_vec()
+        .map(Zeroizing::new)
+        .map_err(|e| CryptoError::Base58Error(e.to_string()))
 }
 
 /// WASM-exposed function for sealing a message using X25519 + XSalsa20-Poly1305.
@@ -105,6 +174,7 @@ fn unseal_internal(
 /// - `sender_secret`: Base58-encoded sender's private key with "sealerSecret_z" prefix
 /// - `recipient_id`: Base58-encoded recipient's public key with "sealer_z" prefix
 /// - `nonce_material`: Raw bytes used to generate the nonce
+///
 /// Returns sealed bytes or throws JsError if sealing fails.
 #[wasm_bindgen(js_name = seal)]
 pub fn seal(
@@ -122,6 +192,7 @@ pub fn seal(
 /// - `recipient_secret`: Base58-encoded recipient's private key with "sealerSecret_z" prefix
 /// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
 /// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+///
 /// Returns unsealed bytes or throws JsError if unsealing fails.
 #[wasm_bindgen(js_name = unseal)]
 pub fn unseal(
@@ -130,7 +201,299 @@ pub fn unseal(
     sender_id: &str,
     nonce_material: &[u8],
 ) -> Result<Box<[u8]>, JsError> {
-    Ok(unseal_internal(sealed_message, recipient_secret, sender_id, nonce_material)?.into())
+    Ok(unseal_internal(
+        sealed_message,
+        recipient_secret,
+        sender_id,
+        nonce_material,
+    )?)
+}
+
+/// WASM-exposed function for sealing a message with a key commitment.
+/// - `message`: Raw bytes to seal
+/// - `sender_secret`: Base58-encoded sender's private key with "sealerSecret_z" prefix
+/// - `recipient_id`: Base58-encoded recipient's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce
+///
+/// Returns sealed bytes, prefixed with the key commitment, or throws JsError if sealing fails.
+#[wasm_bindgen]
+pub fn seal_committing(
+    message: &[u8],
+    sender_secret: &str,
+    recipient_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    Ok(seal_committing_internal(message, sender_secret, recipient_id, nonce_material)?.into())
+}
+
+/// WASM-exposed function for unsealing a message sealed with `seal_committing`.
+/// - `sealed_message`: The sealed bytes, starting with the key commitment
+/// - `recipient_secret`: Base58-encoded recipient's private key with "sealerSecret_z" prefix
+/// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+///
+/// Returns unsealed bytes or throws JsError if the commitment or tag doesn't match.
+#[wasm_bindgen]
+pub fn unseal_committing(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    Ok(unseal_committing_internal(
+        sealed_message,
+        recipient_secret,
+        sender_id,
+        nonce_material,
+    )?)
+}
+
+/// WASM-exposed function for sealing a message wrapped in an envelope.
+/// - `message`: Raw bytes to seal
+/// - `sender_secret`: Base58-encoded sender's private key with "sealerSecret_z" prefix
+/// - `recipient_id`: Base58-encoded recipient's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce
+/// - `options`: Envelope options such as padding and compression, recorded inside the sealed message
+///
+/// Returns sealed bytes or throws JsError if sealing fails.
+#[wasm_bindgen]
+pub fn seal_enveloped(
+    message: &[u8],
+    sender_secret: &str,
+    recipient_id: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(seal_enveloped_internal(
+        message,
+        sender_secret,
+        recipient_id,
+        nonce_material,
+        options,
+    )?
+    .into())
+}
+
+/// WASM-exposed function for unsealing a message sealed with `seal_enveloped`.
+/// - `sealed_message`: The sealed envelope
+/// - `recipient_secret`: Base58-encoded recipient's private key with "sealerSecret_z" prefix
+/// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+///
+/// Returns unsealed bytes or throws JsError if unsealing fails.
+#[wasm_bindgen]
+pub fn unseal_enveloped(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    Ok(unseal_enveloped_internal(
+        sealed_message,
+        recipient_secret,
+        sender_id,
+        nonce_material,
+    )?)
+}
+
+/// WASM-exposed function for unsealing a message sealed with `seal_enveloped`, with a custom decompression limit.
+/// - `sealed_message`: The sealed envelope
+/// - `recipient_secret`: Base58-encoded recipient's private key with "sealerSecret_z" prefix
+/// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
+/// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+/// - `options`: Envelope options; only the decompression limit applies when unsealing
+///
+/// Returns unsealed bytes or throws JsError if unsealing fails.
+#[wasm_bindgen]
+pub fn unseal_enveloped_with_options(
+    sealed_message: &[u8],
+    recipient_secret: &str,
+    sender_id: &str,
+    nonce_material: &[u8],
+    options: &EnvelopeOptions,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(unseal_enveloped_with_options_internal(
+        sealed_message,
+        recipient_secret,
+        sender_id,
+        nonce_material,
+        options,
+    )?)
+}
+
+/// A sealing channel between two agents, with their X25519 shared secret computed once.
+/// This is the equivalent of libsodium's `crypto_box_beforenm`: repeated seal/unseal calls
+/// skip key decoding and the scalar multiplication. The cached secret is zeroized on drop.
+#[wasm_bindgen]
+pub struct SealingChannel {
+    shared_secret: Zeroizing<[u8; 32]>,
+}
+
+#[wasm_bindgen]
+impl SealingChannel {
+    /// Create a channel from our sealer secret and the other agent's sealer ID.
+    /// - `our_secret`: Base58-encoded private key with "sealerSecret_z" prefix
+    /// - `their_id`: Base58-encoded public key with "sealer_z" prefix
+    #[wasm_bindgen(constructor)]
+    pub fn new(our_secret: &str, their_id: &str) -> Result<SealingChannel, JsError> {
+        Ok(Self::new_internal(our_secret, their_id)?)
+    }
+
+    /// Seal a message for the other agent, same as `seal`.
+    /// - `message`: Raw bytes to seal
+    /// - `nonce_material`: Raw bytes used to generate the nonce
+    pub fn seal(&self, message: &[u8], nonce_material: &[u8]) -> Result<Box<[u8]>, JsError> {
+        Ok(self.seal_internal(message, nonce_material)?)
+    }
+
+    /// Unseal a message from the other agent, same as `unseal`.
+    /// - `sealed_message`: The sealed bytes to decrypt
+    /// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+    pub fn unseal(
+        &self,
+        sealed_message: &[u8],
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, JsError> {
+        Ok(self.unseal_internal(sealed_message, nonce_material)?)
+    }
+}
+
+impl SealingChannel {
+    /// Internal constructor from our sealer secret and the other agent's sealer ID.
+    pub fn new_internal(our_secret: &str, their_id: &str) -> Result<Self, CryptoError> {
+        Ok(Self {
+            shared_secret: sealing_shared_secret(our_secret, their_id)?,
+        })
+    }
+
+    /// Internal function to seal a message with the cached shared secret.
+    pub fn seal_internal(
+        &self,
+        message: &[u8],
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, CryptoError> {
+        let nonce = generate_nonce(nonce_material);
+        encrypt_xsalsa20_poly1305(&*self.shared_secret, &nonce, message)
+    }
+
+    /// Internal function to unseal a message with the cached shared secret.
+    pub fn unseal_internal(
+        &self,
+        sealed_message: &[u8],
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, CryptoError> {
+        let nonce = generate_nonce(nonce_material);
+        decrypt_xsalsa20_poly1305(&*self.shared_secret, &nonce, sealed_message)
+    }
+}
+
+/// An X25519 sealer secret parsed once for repeated sealing and unsealing.
+/// The decoded private key is zeroized on drop.
+#[wasm_bindgen]
+pub struct SealerKey {
+    private_key: Zeroizing<[u8; 32]>,
+    id: String,
+}
+
+#[wasm_bindgen]
+impl SealerKey {
+    /// Parse a sealer secret.
+    /// - `secret`: Base58-encoded private key with "sealerSecret_z" prefix
+    #[wasm_bindgen(constructor)]
+    pub fn new(secret: &str) -> Result<SealerKey, JsError> {
+        Ok(Self::new_internal(secret)?)
+    }
+
+    /// The "sealer_z" ID of this key.
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    /// Seal a message for a recipient, same as `seal`.
+    /// - `message`: Raw bytes to seal
+    /// - `recipient_id`: Base58-encoded recipient's public key with "sealer_z" prefix
+    /// - `nonce_material`: Raw bytes used to generate the nonce
+    pub fn seal(
+        &self,
+        message: &[u8],
+        recipient_id: &str,
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, JsError> {
+        Ok(self.seal_internal(message, recipient_id, nonce_material)?)
+    }
+
+    /// Unseal a message from a sender, same as `unseal`.
+    /// - `sealed_message`: The sealed bytes to decrypt
+    /// - `sender_id`: Base58-encoded sender's public key with "sealer_z" prefix
+    /// - `nonce_material`: Raw bytes used to generate the nonce (must match sealing)
+    pub fn unseal(
+        &self,
+        sealed_message: &[u8],
+        sender_id: &str,
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, JsError> {
+        Ok(self.unseal_internal(sealed_message, sender_id, nonce_material)?)
+    }
+
+    /// Open a `SealingChannel` to another agent, caching the shared secret as well.
+    /// - `their_id`: Base58-encoded public key with "sealer_z" prefix
+    pub fn channel(&self, their_id: &str) -> Result<SealingChannel, JsError> {
+        Ok(self.channel_internal(their_id)?)
+    }
+}
+
+impl SealerKey {
+    /// Internal constructor from a "sealerSecret_z" string.
+    pub fn new_internal(secret: &str) -> Result<Self, CryptoError> {
+        let secret_bytes = decode_sealer_secret(secret)?;
+        let private_key: [u8; 32] = secret_bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidKeyLength(32, secret_bytes.len()))?;
+        let id = format!(
+            "sealer_z{}",
+            bs58::encode(x25519_public_key_internal(&private_key)?).into_string()
+        );
+        Ok(Self {
+            private_key: Zeroizing::new(private_key),
+            id,
+        })
+    }
+
+    fn shared_secret(&self, their_id: &str) -> Result<Zeroizing<[u8; 32]>, CryptoError> {
+        x25519_diffie_hellman_internal(&*self.private_key, &parse_sealer_id_internal(their_id)?)
+            .map(Zeroizing::new)
+    }
+
+    /// Internal function to seal a message for a recipient.
+    pub fn seal_internal(
+        &self,
+        message: &[u8],
+        recipient_id: &str,
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, CryptoError> {
+        let nonce = generate_nonce(nonce_material);
+        encrypt_xsalsa20_poly1305(&*self.shared_secret(recipient_id)?, &nonce, message)
+    }
+
+    /// Internal function to unseal a message from a sender.
+    pub fn unseal_internal(
+        &self,
+        sealed_message: &[u8],
+        sender_id: &str,
+        nonce_material: &[u8],
+    ) -> Result<Box<[u8]>, CryptoError> {
+        let nonce = generate_nonce(nonce_material);
+        decrypt_xsalsa20_poly1305(&*self.shared_secret(sender_id)?, &nonce, sealed_message)
+    }
+
+    /// Internal function to open a `SealingChannel` to another agent.
+    pub fn channel_internal(&self, their_id: &str) -> Result<SealingChannel, CryptoError> {
+        Ok(SealingChannel {
+            shared_secret: self.shared_secret(their_id)?,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -196,5 +559,216 @@ mod tests {
             nonce_material,
         );
         assert!(result.is_err());
+
+        // A low-order recipient ID would force a known all-zero shared secret,
+        // so it is rejected when the ID is parsed
+        let sender_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(new_x25519_private_key()).into_string()
+        );
+        let low_order_id = format!("sealer_z{}", bs58::encode([0u8; 32]).into_string());
+        assert!(matches!(
+            seal_internal(message, &sender_secret, &low_order_id, nonce_material),
+            Err(CryptoError::InvalidPublicKey(_))
+        ));
+        assert!(matches!(
+            SealingChannel::new_internal(&sender_secret, &low_order_id),
+            Err(CryptoError::InvalidPublicKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_seal_unseal_committing() {
+        let sender_private = new_x25519_private_key();
+        let sender_public = x25519_public_key_internal(&sender_private).unwrap();
+        let recipient_private = new_x25519_private_key();
+        let recipient_public = x25519_public_key_internal(&recipient_private).unwrap();
+        let sender_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(&sender_private).into_string()
+        );
+        let sender_id = format!("sealer_z{}", bs58::encode(&sender_public).into_string());
+        let recipient_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(&recipient_private).into_string()
+        );
+        let recipient_id = format!("sealer_z{}", bs58::encode(&recipient_public).into_string());
+
+        let message = b"Secret message";
+        let nonce_material = b"test_nonce_material";
+
+        let sealed =
+            seal_committing_internal(message, &sender_secret, &recipient_id, nonce_material)
+                .unwrap();
+        let unsealed =
+            unseal_committing_internal(&sealed, &recipient_secret, &sender_id, nonce_material)
+                .unwrap();
+        assert_eq!(&*unsealed, message);
+
+        // A third party's key doesn't match the commitment
+        let other_private = new_x25519_private_key();
+        let other_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(&other_private).into_string()
+        );
+        assert!(matches!(
+            unseal_committing_internal(&sealed, &other_secret, &sender_id, nonce_material),
+            Err(CryptoError::KeyCommitmentMismatch)
+        ));
+
+        // Committing and non-committing formats are not interchangeable
+        assert!(unseal_internal(&sealed, &recipient_secret, &sender_id, nonce_material).is_err());
+    }
+
+    #[test]
+    fn test_seal_unseal_enveloped() {
+        use crate::crypto::envelope::Padding;
+
+        let private = new_x25519_private_key();
+        let public = x25519_public_key_internal(&private).unwrap();
+        let secret = format!("sealerSecret_z{}", bs58::encode(&private).into_string());
+        let id = format!("sealer_z{}", bs58::encode(&public).into_string());
+        let nonce_material = b"test_nonce_material";
+
+        let mut options = EnvelopeOptions::new();
+        options.set_padding(Padding::Padme);
+        let sealed_a =
+            seal_enveloped_internal(&[1u8; 995], &secret, &id, nonce_material, &options).unwrap();
+        let sealed_b =
+            seal_enveloped_internal(&[1u8; 1000], &secret, &id, nonce_material, &options).unwrap();
+        assert_eq!(sealed_a.len(), sealed_b.len());
+
+        let unsealed = unseal_enveloped_internal(&sealed_a, &secret, &id, nonce_material).unwrap();
+        assert_eq!(&*unsealed, &[1u8; 995]);
+    }
+
+    #[test]
+    fn test_seal_unseal_compressed() {
+        let private = new_x25519_private_key();
+        let public = x25519_public_key_internal(&private).unwrap();
+        let secret = format!("sealerSecret_z{}", bs58::encode(&private).into_string());
+        let id = format!("sealer_z{}", bs58::encode(&public).into_string());
+        let nonce_material = b"test_nonce_material";
+        let text = "a long, repetitive text value ".repeat(100);
+
+        let mut options = EnvelopeOptions::new();
+        options.set_compression(true);
+        let sealed =
+            seal_enveloped_internal(text.as_bytes(), &secret, &id, nonce_material, &options)
+                .unwrap();
+        assert!(sealed.len() < text.len() / 4);
+
+        let unsealed = unseal_enveloped_internal(&sealed, &secret, &id, nonce_material).unwrap();
+        assert_eq!(&*unsealed, text.as_bytes());
+
+        // The decompression limit is enforced by the receiver
+        let mut limited = EnvelopeOptions::new();
+        limited.set_max_decompressed_len(100);
+        assert!(matches!(
+            unseal_enveloped_with_options_internal(&sealed, &secret, &id, nonce_material, &limited),
+            Err(CryptoError::InvalidEnvelope(_))
+        ));
+    }
+
+    #[test]
+    fn test_sealing_channel() {
+        let alice_private = new_x25519_private_key();
+        let alice_public = x25519_public_key_internal(&alice_private).unwrap();
+        let bob_private = new_x25519_private_key();
+        let bob_public = x25519_public_key_internal(&bob_private).unwrap();
+        let alice_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(&alice_private).into_string()
+        );
+        let alice_id = format!("sealer_z{}", bs58::encode(&alice_public).into_string());
+        let bob_secret = format!("sealerSecret_z{}", bs58::encode(&bob_private).into_string());
+        let bob_id = format!("sealer_z{}", bs58::encode(&bob_public).into_string());
+
+        let alice_to_bob = SealingChannel::new_internal(&alice_secret, &bob_id).unwrap();
+        let bob_to_alice = SealingChannel::new_internal(&bob_secret, &alice_id).unwrap();
+
+        for i in 0..3 {
+            let message = format!("message {i}");
+            let nonce_material = format!("nonce {i}");
+
+            // Channel output is identical to one-shot sealing
+            let sealed = alice_to_bob
+                .seal_internal(message.as_bytes(), nonce_material.as_bytes())
+                .unwrap();
+            assert_eq!(
+                sealed.to_vec(),
+                seal_internal(
+                    message.as_bytes(),
+                    &alice_secret,
+                    &bob_id,
+                    nonce_material.as_bytes()
+                )
+                .unwrap()
+            );
+
+            let unsealed = bob_to_alice
+                .unseal_internal(&sealed, nonce_material.as_bytes())
+                .unwrap();
+            assert_eq!(&*unsealed, message.as_bytes());
+            assert_eq!(
+                &*unseal_internal(&sealed, &bob_secret, &alice_id, nonce_material.as_bytes())
+                    .unwrap(),
+                message.as_bytes()
+            );
+        }
+
+        // Wrong nonce material fails authentication
+        let sealed = alice_to_bob.seal_internal(b"hi", b"nonce").unwrap();
+        assert!(matches!(
+            bob_to_alice.unseal_internal(&sealed, b"other nonce"),
+            Err(CryptoError::WrongTag)
+        ));
+
+        // Invalid keys are rejected when the channel is created
+        assert!(SealingChannel::new_internal("invalid_key", &bob_id).is_err());
+        assert!(SealingChannel::new_internal(&alice_secret, "sealer_z!!!!").is_err());
+    }
+
+    #[test]
+    fn test_sealer_key() {
+        let alice_private = new_x25519_private_key();
+        let bob_private = new_x25519_private_key();
+        let alice_secret = format!(
+            "sealerSecret_z{}",
+            bs58::encode(&alice_private).into_string()
+        );
+        let bob_secret = format!("sealerSecret_z{}", bs58::encode(&bob_private).into_string());
+        let alice = SealerKey::new_internal(&alice_secret).unwrap();
+        let bob = SealerKey::new_internal(&bob_secret).unwrap();
+        assert_eq!(
+            alice.id(),
+            crate::crypto::x25519::get_sealer_id_internal(&alice_secret).unwrap()
+        );
+
+        let sealed = alice
+            .seal_internal(b"Secret message", &bob.id(), b"nonce")
+            .unwrap();
+        assert_eq!(
+            sealed.to_vec(),
+            seal_internal(b"Secret message", &alice_secret, &bob.id(), b"nonce").unwrap()
+        );
+        assert_eq!(
+            &*bob.unseal_internal(&sealed, &alice.id(), b"nonce").unwrap(),
+            b"Secret message"
+        );
+
+        // Channels opened from a parsed key interoperate with the key itself
+        let channel = bob.channel_internal(&alice.id()).unwrap();
+        assert_eq!(
+            &*channel.unseal_internal(&sealed, b"nonce").unwrap(),
+            b"Secret message"
+        );
+
+        assert!(SealerKey::new_internal("invalid_key").is_err());
+        assert!(matches!(
+            SealerKey::new_internal("sealerSecret_z1111"),
+            Err(CryptoError::InvalidKeyLength(32, _))
+        ));
+        assert!(alice.seal_internal(b"hi", "invalid_id", b"nonce").is_err());
     }
 }
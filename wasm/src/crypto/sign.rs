@@ -1,17 +1,49 @@
+use crate::crypto::agent::parse_signer_id_internal;
 use crate::crypto::ed25519::{
     ed25519_sign_internal, ed25519_verify_internal, ed25519_verifying_key_internal,
 };
+#[cfg(feature = "ml-dsa")]
+use crate::crypto::ml_dsa::{
+    ml_dsa_sign_internal, ml_dsa_verify_internal, ml_dsa_verifying_key_internal,
+};
 use crate::error::CryptoError;
+use crate::json::stable_stringify::{
+    stable_stringify_internal, stable_stringify_js_internal, stable_stringify_str_internal,
+};
+use crate::prefix::SIGNER_SECRET_PREFIX;
+#[cfg(feature = "ml-dsa")]
+use crate::prefix::{
+    decode_prefixed, encode_prefixed, ML_DSA_SIGNATURE_PREFIX, ML_DSA_SIGNER_ID_PREFIX,
+    ML_DSA_SIGNER_SECRET_PREFIX,
+};
 use bs58;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+use serde_json::Value;
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "ml-dsa")]
+use zeroize::Zeroizing;
 
 /// Internal function to sign a message using Ed25519.
 /// - `message`: Raw bytes to sign
 /// - `secret`: Base58-encoded signing key with "signerSecret_z" prefix
+///
 /// Returns base58-encoded signature with "signature_z" prefix or error string.
+/// With the `ml-dsa` feature, a "mlDsaSignerSecret_z" secret signs with ML-DSA-65 instead
+/// and returns a "mlDsaSignature_z" signature.
 pub fn sign_internal(message: &[u8], secret: &str) -> Result<String, CryptoError> {
-    let secret_bytes = bs58::decode(secret.strip_prefix("signerSecret_z").ok_or(
-        CryptoError::InvalidPrefix("signer secret", "signerSecret_z"),
+    #[cfg(feature = "ml-dsa")]
+    if secret.starts_with(ML_DSA_SIGNER_SECRET_PREFIX) {
+        let secret_bytes = Zeroizing::new(decode_prefixed(
+            secret,
+            ML_DSA_SIGNER_SECRET_PREFIX,
+            "ML-DSA signer secret",
+        )?);
+        let signature = ml_dsa_sign_internal(&secret_bytes, message)?;
+        return Ok(encode_prefixed(ML_DSA_SIGNATURE_PREFIX, &signature));
+    }
+
+    let secret_bytes = bs58::decode(secret.strip_prefix(SIGNER_SECRET_PREFIX).ok_or(
+        CryptoError::InvalidPrefix("signer secret", SIGNER_SECRET_PREFIX),
     )?)
     .into_vec()
     .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
@@ -28,8 +60,18 @@ pub fn sign_internal(message: &[u8], secret: &str) -> Result<String, CryptoError
 /// - `signature`: Base58-encoded signature with "signature_z" prefix
 /// - `message`: Raw bytes that were signed
 /// - `id`: Base58-encoded verifying key with "signer_z" prefix
+///
 /// Returns true if signature is valid, false otherwise, or error string if formats are invalid.
+/// With the `ml-dsa` feature, a "mlDsaSigner_z" ID verifies a "mlDsaSignature_z" signature.
 pub fn verify_internal(signature: &str, message: &[u8], id: &str) -> Result<bool, CryptoError> {
+    #[cfg(feature = "ml-dsa")]
+    if id.starts_with(ML_DSA_SIGNER_ID_PREFIX) {
+        let signature_bytes =
+            decode_prefixed(signature, ML_DSA_SIGNATURE_PREFIX, "ML-DSA signature")?;
+        let verifying_key = decode_prefixed(id, ML_DSA_SIGNER_ID_PREFIX, "ML-DSA signer ID")?;
+        return ml_dsa_verify_internal(&verifying_key, message, &signature_bytes);
+    }
+
     let signature_bytes = bs58::decode(
         signature
             .strip_prefix("signature_z")
@@ -38,12 +80,7 @@ pub fn verify_internal(signature: &str, message: &[u8], id: &str) -> Result<bool
     .into_vec()
     .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
 
-    let verifying_key = bs58::decode(
-        id.strip_prefix("signer_z")
-            .ok_or(CryptoError::InvalidPrefix("signer_z", "signer ID"))?,
-    )
-    .into_vec()
-    .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+    let verifying_key = parse_signer_id_internal(id)?;
 
     ed25519_verify_internal(&verifying_key, message, &signature_bytes)
         .map_err(|e| CryptoError::InvalidVerifyingKey(e.to_string()))
@@ -51,10 +88,23 @@ pub fn verify_internal(signature: &str, message: &[u8], id: &str) -> Result<bool
 
 /// Internal function to derive a signer ID from a signing key.
 /// - `secret`: Base58-encoded signing key with "signerSecret_z" prefix
+///
 /// Returns base58-encoded verifying key with "signer_z" prefix or error string.
+/// With the `ml-dsa` feature, a "mlDsaSignerSecret_z" secret gives a "mlDsaSigner_z" ID.
 pub fn get_signer_id_internal(secret: &str) -> Result<String, CryptoError> {
-    let secret_bytes = bs58::decode(secret.strip_prefix("signerSecret_z").ok_or(
-        CryptoError::InvalidPrefix("signerSecret_z", "signer secret"),
+    #[cfg(feature = "ml-dsa")]
+    if secret.starts_with(ML_DSA_SIGNER_SECRET_PREFIX) {
+        let secret_bytes = Zeroizing::new(decode_prefixed(
+            secret,
+            ML_DSA_SIGNER_SECRET_PREFIX,
+            "ML-DSA signer secret",
+        )?);
+        let verifying_key = ml_dsa_verifying_key_internal(&secret_bytes)?;
+        return Ok(encode_prefixed(ML_DSA_SIGNER_ID_PREFIX, &verifying_key));
+    }
+
+    let secret_bytes = bs58::decode(secret.strip_prefix(SIGNER_SECRET_PREFIX).ok_or(
+        CryptoError::InvalidPrefix(SIGNER_SECRET_PREFIX, "signer secret"),
     )?)
     .into_vec()
     .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
@@ -68,9 +118,29 @@ pub fn get_signer_id_internal(secret: &str) -> Result<String, CryptoError> {
     ))
 }
 
+/// Internal function to sign a JSON value the way cojson does.
+/// - `value`: The JSON value to sign
+/// - `secret`: Base58-encoded signing key with "signerSecret_z" prefix
+///
+/// Returns base58-encoded signature with "signature_z" prefix over the value's canonical JSON.
+pub fn sign_json_internal(value: &Value, secret: &str) -> Result<String, CryptoError> {
+    sign_internal(stable_stringify_internal(value).as_bytes(), secret)
+}
+
+/// Internal function to verify a signature over a JSON value.
+/// - `signature`: Base58-encoded signature with "signature_z" prefix
+/// - `value`: The JSON value that was signed
+/// - `id`: Base58-encoded verifying key with "signer_z" prefix
+///
+/// Returns true if the signature is valid for the value's canonical JSON, false otherwise.
+pub fn verify_json_internal(signature: &str, value: &Value, id: &str) -> Result<bool, CryptoError> {
+    verify_internal(signature, stable_stringify_internal(value).as_bytes(), id)
+}
+
 /// WASM-exposed function to sign a message using Ed25519.
 /// - `message`: Raw bytes to sign
 /// - `secret`: Raw Ed25519 signing key bytes
+///
 /// Returns base58-encoded signature with "signature_z" prefix or throws JsError if signing fails.
 #[wasm_bindgen(js_name = sign)]
 pub fn sign(message: &[u8], secret: &[u8]) -> Result<String, JsError> {
@@ -83,6 +153,7 @@ pub fn sign(message: &[u8], secret: &[u8]) -> Result<String, JsError> {
 /// - `signature`: Raw signature bytes
 /// - `message`: Raw bytes that were signed
 /// - `id`: Raw Ed25519 verifying key bytes
+///
 /// Returns true if signature is valid, false otherwise, or throws JsError if verification fails.
 #[wasm_bindgen(js_name = verify)]
 pub fn verify(signature: &[u8], message: &[u8], id: &[u8]) -> Result<bool, JsError> {
@@ -95,6 +166,7 @@ pub fn verify(signature: &[u8], message: &[u8], id: &[u8]) -> Result<bool, JsErr
 
 /// WASM-exposed function to derive a signer ID from a signing key.
 /// - `secret`: Raw Ed25519 signing key bytes
+///
 /// Returns base58-encoded verifying key with "signer_z" prefix or throws JsError if derivation fails.
 #[wasm_bindgen(js_name = get_signer_id)]
 pub fn get_signer_id(secret: &[u8]) -> Result<String, JsError> {
@@ -103,6 +175,154 @@ pub fn get_signer_id(secret: &[u8]) -> Result<String, JsError> {
     get_signer_id_internal(secret_str).map_err(|e| JsError::new(&e.to_string()))
 }
 
+/// WASM-exposed function to sign a JavaScript value the way cojson does.
+/// - `value`: Any JSON-compatible JavaScript value
+/// - `secret`: Base58-encoded signing key with "signerSecret_z" prefix
+///
+/// Returns base58-encoded signature with "signature_z" prefix or throws JsError if signing fails.
+#[wasm_bindgen(js_name = sign_json)]
+pub fn sign_json(value: JsValue, secret: &str) -> Result<String, JsError> {
+    Ok(sign_internal(
+        stable_stringify_js_internal(&value)?.as_bytes(),
+        secret,
+    )?)
+}
+
+/// WASM-exposed function to sign JSON text the way cojson does.
+/// - `json`: JSON text in any formatting and key order
+/// - `secret`: Base58-encoded signing key with "signerSecret_z" prefix
+///
+/// Returns base58-encoded signature with "signature_z" prefix or throws JsError if signing fails.
+#[wasm_bindgen(js_name = sign_json_str)]
+pub fn sign_json_str(json: &str, secret: &str) -> Result<String, JsError> {
+    Ok(sign_internal(
+        stable_stringify_str_internal(json)?.as_bytes(),
+        secret,
+    )?)
+}
+
+/// WASM-exposed function to verify a signature over a JavaScript value.
+/// - `signature`: Base58-encoded signature with "signature_z" prefix
+/// - `value`: Any JSON-compatible JavaScript value
+/// - `id`: Base58-encoded verifying key with "signer_z" prefix
+///
+/// Returns true if signature is valid, false otherwise, or throws JsError if verification fails.
+#[wasm_bindgen(js_name = verify_json)]
+pub fn verify_json(signature: &str, value: JsValue, id: &str) -> Result<bool, JsError> {
+    Ok(verify_internal(
+        signature,
+        stable_stringify_js_internal(&value)?.as_bytes(),
+        id,
+    )?)
+}
+
+/// WASM-exposed function to verify a signature over JSON text.
+/// - `signature`: Base58-encoded signature with "signature_z" prefix
+/// - `json`: JSON text in any formatting and key order
+/// - `id`: Base58-encoded verifying key with "signer_z" prefix
+///
+/// Returns true if signature is valid, false otherwise, or throws JsError if verification fails.
+#[wasm_bindgen(js_name = verify_json_str)]
+pub fn verify_json_str(signature: &str, json: &str, id: &str) -> Result<bool, JsError> {
+    Ok(verify_internal(
+        signature,
+        stable_stringify_str_internal(json)?.as_bytes(),
+        id,
+    )?)
+}
+
+/// An Ed25519 signer secret parsed once for repeated signing.
+/// Constructing it decodes the "signerSecret_z" string and derives the public key,
+/// so `sign` only has to compute the signature.
+#[wasm_bindgen]
+pub struct SignerKey {
+    signing_key: SigningKey,
+    id: String,
+}
+
+#[wasm_bindgen]
+impl SignerKey {
+    /// Parse a signer secret.
+    /// - `secret`: Base58-encoded signing key with "signerSecret_z" prefix
+    #[wasm_bindgen(constructor)]
+    pub fn new(secret: &str) -> Result<SignerKey, JsError> {
+        Ok(Self::new_internal(secret)?)
+    }
+
+    /// The "signer_z" ID of this key.
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    /// Sign a message, same as `sign`.
+    /// Returns base58-encoded signature with "signature_z" prefix.
+    pub fn sign(&self, message: &[u8]) -> String {
+        let signature = self.signing_key.sign(message);
+        format!(
+            "signature_z{}",
+            bs58::encode(signature.to_bytes()).into_string()
+        )
+    }
+
+    /// Sign a JavaScript value the way cojson does, same as `sign_json`.
+    pub fn sign_json(&self, value: JsValue) -> Result<String, JsError> {
+        Ok(self.sign(stable_stringify_js_internal(&value)?.as_bytes()))
+    }
+
+    /// Verify a signature made with this key.
+    /// - `signature`: Base58-encoded signature with "signature_z" prefix
+    /// - `message`: Raw bytes that were signed
+    ///
+    /// Returns true if signature is valid, false otherwise, or throws JsError if its format is invalid.
+    pub fn verify(&self, signature: &str, message: &[u8]) -> Result<bool, JsError> {
+        Ok(self.verify_internal(signature, message)?)
+    }
+}
+
+impl SignerKey {
+    /// Internal constructor from a "signerSecret_z" string.
+    pub fn new_internal(secret: &str) -> Result<Self, CryptoError> {
+        let secret_bytes = bs58::decode(secret.strip_prefix(SIGNER_SECRET_PREFIX).ok_or(
+            CryptoError::InvalidPrefix(SIGNER_SECRET_PREFIX, "signer secret"),
+        )?)
+        .into_vec()
+        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+        let key_bytes: [u8; 32] = secret_bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidKeyLength(32, secret_bytes.len()))?;
+
+        let signing_key = SigningKey::from_bytes(&key_bytes);
+        let id = format!(
+            "signer_z{}",
+            bs58::encode(signing_key.verifying_key().to_bytes()).into_string()
+        );
+        Ok(Self { signing_key, id })
+    }
+
+    /// Internal function to verify a signature made with this key.
+    pub fn verify_internal(&self, signature: &str, message: &[u8]) -> Result<bool, CryptoError> {
+        let signature_bytes = bs58::decode(
+            signature
+                .strip_prefix("signature_z")
+                .ok_or(CryptoError::InvalidPrefix("signature_z", "signature"))?,
+        )
+        .into_vec()
+        .map_err(|e| CryptoError::Base58Error(e.to_string()))?;
+        let signature_bytes: [u8; 64] = signature_bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignatureLength)?;
+
+        Ok(self
+            .signing_key
+            .verifying_key()
+            .verify(message, &Signature::from_bytes(&signature_bytes))
+            .is_ok())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,4 +401,98 @@ mod tests {
         let result = get_signer_id_internal("signerSecret_z!!!invalid!!!");
         assert!(matches!(result, Err(CryptoError::Base58Error(_))));
     }
+
+    #[test]
+    fn test_sign_and_verify_json() {
+        let signing_key = new_ed25519_signing_key();
+        let secret = format!("signerSecret_z{}", bs58::encode(&signing_key).into_string());
+        let signer_id = get_signer_id_internal(&secret).unwrap();
+
+        let value: Value = serde_json::from_str(r#"{"tx":{"txIndex":3},"in":"co_z1"}"#).unwrap();
+        let signature = sign_json_internal(&value, &secret).unwrap();
+
+        // The signature covers the canonical JSON bytes
+        let canonical = br#"{"in":"co_z1","tx":{"txIndex":3}}"#;
+        assert_eq!(signature, sign_internal(canonical, &secret).unwrap());
+        assert!(verify_internal(&signature, canonical, &signer_id).unwrap());
+
+        // Differently formatted but equivalent JSON verifies
+        let reformatted: Value =
+            serde_json::from_str(r#"{ "in": "co_z1", "tx": { "txIndex": 3.0 } }"#).unwrap();
+        assert!(verify_json_internal(&signature, &reformatted, &signer_id).unwrap());
+
+        // A different value does not
+        let changed: Value = serde_json::from_str(r#"{"in":"co_z1","tx":{"txIndex":4}}"#).unwrap();
+        assert!(!verify_json_internal(&signature, &changed, &signer_id).unwrap());
+    }
+
+    #[test]
+    fn test_signer_key() {
+        let signing_key = new_ed25519_signing_key();
+        let secret = format!("signerSecret_z{}", bs58::encode(&signing_key).into_string());
+        let key = SignerKey::new_internal(&secret).unwrap();
+        assert_eq!(key.id(), get_signer_id_internal(&secret).unwrap());
+
+        // Signatures match the one-shot functions and verify both ways
+        let signature = key.sign(b"hello world");
+        assert_eq!(signature, sign_internal(b"hello world", &secret).unwrap());
+        assert!(verify_internal(&signature, b"hello world", &key.id()).unwrap());
+        assert!(key.verify_internal(&signature, b"hello world").unwrap());
+        assert!(!key.verify_internal(&signature, b"other message").unwrap());
+        assert!(matches!(
+            key.verify_internal("signature_z111", b"hello world"),
+            Err(CryptoError::InvalidSignatureLength)
+        ));
+
+        // Invalid secrets
+        assert!(matches!(
+            SignerKey::new_internal("invalid_secret"),
+            Err(CryptoError::InvalidPrefix(
+                "signerSecret_z",
+                "signer secret"
+            ))
+        ));
+        assert!(matches!(
+            SignerKey::new_internal("signerSecret_z1111"),
+            Err(CryptoError::InvalidKeyLength(32, _))
+        ));
+    }
+
+    #[cfg(feature = "ml-dsa")]
+    #[test]
+    fn test_sign_and_verify_ml_dsa() {
+        use crate::crypto::ml_dsa::new_ml_dsa_signing_key;
+        use crate::prefix::encode_prefixed;
+
+        let secret = encode_prefixed(ML_DSA_SIGNER_SECRET_PREFIX, &new_ml_dsa_signing_key());
+        let id = get_signer_id_internal(&secret).unwrap();
+        assert!(id.starts_with(ML_DSA_SIGNER_ID_PREFIX));
+
+        let message = b"hello world";
+        let signature = sign_internal(message, &secret).unwrap();
+        assert!(signature.starts_with(ML_DSA_SIGNATURE_PREFIX));
+        assert!(verify_internal(&signature, message, &id).unwrap());
+        assert!(!verify_internal(&signature, b"other message", &id).unwrap());
+
+        // The JSON helpers dispatch the same way
+        let value = serde_json::json!({"b": 1, "a": [true, null]});
+        let json_signature = sign_json_internal(&value, &secret).unwrap();
+        assert!(verify_json_internal(&json_signature, &value, &id).unwrap());
+
+        // Signatures and IDs of the two schemes can't be mixed
+        let ed25519_secret = format!(
+            "signerSecret_z{}",
+            bs58::encode(new_ed25519_signing_key()).into_string()
+        );
+        let ed25519_id = get_signer_id_internal(&ed25519_secret).unwrap();
+        let ed25519_signature = sign_internal(message, &ed25519_secret).unwrap();
+        assert!(matches!(
+            verify_internal(&ed25519_signature, message, &id),
+            Err(CryptoError::InvalidPrefix(ML_DSA_SIGNATURE_PREFIX, _))
+        ));
+        assert!(matches!(
+            verify_internal(&signature, message, &ed25519_id),
+            Err(CryptoError::InvalidPrefix("signature_z", _))
+        ));
+    }
 }
@@ -0,0 +1,324 @@
+use crate::crypto::encrypt::parse_key_secret;
+use crate::crypto::xsalsa20::{decrypt_xsalsa20_poly1305, encrypt_xsalsa20_poly1305};
+use crate::error::CryptoError;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use wasm_bindgen::prelude::*;
+use zeroize::Zeroizing;
+
+/// Length of the random stream header, which must be sent ahead of the first chunk.
+pub const STREAM_HEADER_LENGTH: usize = 24;
+
+/// BLAKE3 derive_key context for per-stream keys.
+const STREAM_KEY_CONTEXT: &str = "jazz-crypto-rs 2025 stream key";
+
+/// Nonce flag byte for every chunk except the last one.
+const CHUNK_FLAG_MORE: u8 = 0;
+/// Nonce flag byte for the last chunk of a stream.
+const CHUNK_FLAG_FINAL: u8 = 1;
+
+/// Derive the key for a single stream from the key secret and the stream header.
+/// Every stream gets a fresh key, so per-chunk nonces can simply count up from zero.
+fn stream_key(key_secret: &str, header: &[u8]) -> Result<Zeroizing<[u8; 32]>, CryptoError> {
+    let key = Zeroizing::new(parse_key_secret(key_secret)?);
+    if key.len() != 32 {
+        return Err(CryptoError::InvalidKeyLength(32, key.len()));
+    }
+    if header.len() != STREAM_HEADER_LENGTH {
+        return Err(CryptoError::InvalidLength(
+            "stream header",
+            STREAM_HEADER_LENGTH,
+            header.len(),
+        ));
+    }
+    let mut hasher = blake3::Hasher::new_derive_key(STREAM_KEY_CONTEXT);
+    hasher.update(&key);
+    hasher.update(header);
+    Ok(Zeroizing::new(*hasher.finalize().as_bytes()))
+}
+
+/// Build the nonce for a chunk: the chunk counter (u64 LE) followed by the final-chunk flag.
+fn chunk_nonce(counter: u64, flag: u8) -> [u8; 24] {
+    let mut nonce = [0u8; 24];
+    nonce[..8].copy_from_slice(&counter.to_le_bytes());
+    nonce[8] = flag;
+    nonce
+}
+
+/// Encrypts a stream of chunks with XSalsa20-Poly1305, binding each chunk to its position.
+/// Chunks are authenticated under a nonce derived from their index and whether they are
+/// the last chunk, so reordered, duplicated, dropped or truncated chunks fail to decrypt.
+#[wasm_bindgen]
+pub struct StreamEncryptor {
+    key: Zeroizing<[u8; 32]>,
+    header: [u8; STREAM_HEADER_LENGTH],
+    counter: u64,
+    finished: bool,
+}
+
+#[wasm_bindgen]
+impl StreamEncryptor {
+    /// Start a new stream with a random header.
+    /// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+    #[wasm_bindgen(constructor)]
+    pub fn new(key_secret: &str) -> Result<StreamEncryptor, JsError> {
+        Ok(Self::new_internal(key_secret)?)
+    }
+
+    /// The stream header, to be stored or sent before the encrypted chunks.
+    #[wasm_bindgen(getter)]
+    pub fn header(&self) -> Box<[u8]> {
+        self.header.into()
+    }
+
+    /// Encrypt the next chunk of the stream.
+    /// Returns the encrypted chunk or throws JsError if the stream was already finished.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Box<[u8]>, JsError> {
+        Ok(self.push_internal(chunk, false)?)
+    }
+
+    /// Encrypt the last chunk of the stream, which may be empty.
+    /// Returns the encrypted chunk or throws JsError if the stream was already finished.
+    pub fn finish(&mut self, chunk: &[u8]) -> Result<Box<[u8]>, JsError> {
+        Ok(self.push_internal(chunk, true)?)
+    }
+}
+
+impl StreamEncryptor {
+    /// Internal constructor for a stream with a random header.
+    pub fn new_internal(key_secret: &str) -> Result<Self, CryptoError> {
+        let mut header = [0u8; STREAM_HEADER_LENGTH];
+        OsRng.fill_bytes(&mut header);
+        Ok(Self {
+            key: stream_key(key_secret, &header)?,
+            header,
+            counter: 0,
+            finished: false,
+        })
+    }
+
+    /// Internal function to encrypt the next chunk, marking it as the last one if `last` is true.
+    pub fn push_internal(&mut self, chunk: &[u8], last: bool) -> Result<Box<[u8]>, CryptoError> {
+        if self.finished {
+            return Err(CryptoError::InvalidStream("stream already finished"));
+        }
+        let flag = if last {
+            CHUNK_FLAG_FINAL
+        } else {
+            CHUNK_FLAG_MORE
+        };
+        let ciphertext =
+            encrypt_xsalsa20_poly1305(&*self.key, &chunk_nonce(self.counter, flag), chunk)?;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or(CryptoError::InvalidStream("too many chunks"))?;
+        self.finished = last;
+        Ok(ciphertext)
+    }
+}
+
+/// Decrypts a stream produced by `StreamEncryptor`, chunk by chunk and in order.
+#[wasm_bindgen]
+pub struct StreamDecryptor {
+    key: Zeroizing<[u8; 32]>,
+    counter: u64,
+    finished: bool,
+}
+
+#[wasm_bindgen]
+impl StreamDecryptor {
+    /// Start decrypting a stream.
+    /// - `key_secret`: A base58-encoded key secret with "keySecret_z" prefix
+    /// - `header`: The stream header produced by the encryptor
+    #[wasm_bindgen(constructor)]
+    pub fn new(key_secret: &str, header: &[u8]) -> Result<StreamDecryptor, JsError> {
+        Ok(Self::new_internal(key_secret, header)?)
+    }
+
+    /// Decrypt the next chunk of the stream.
+    /// Returns the decrypted chunk or throws JsError if the chunk is out of order,
+    /// tampered with, or arrives after the last chunk.
+    pub fn pull(&mut self, chunk: &[u8]) -> Result<Box<[u8]>, JsError> {
+        Ok(self.pull_internal(chunk)?)
+    }
+
+    /// Whether the last chunk of the stream has been decrypted.
+    #[wasm_bindgen(getter)]
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Check that the stream ended properly once the input is exhausted.
+    /// Throws JsError if the last chunk hasn't been seen, i.e. the stream was truncated.
+    pub fn end(&self) -> Result<(), JsError> {
+        Ok(self.end_internal()?)
+    }
+}
+
+impl StreamDecryptor {
+    /// Internal constructor from the key secret and stream header.
+    pub fn new_internal(key_secret: &str, header: &[u8]) -> Result<Self, CryptoError> {
+        Ok(Self {
+            key: stream_key(key_secret, header)?,
+            counter: 0,
+            finished: false,
+        })
+    }
+
+    /// Internal function to decrypt the next chunk.
+    /// The chunk is first tried as a regular chunk and then as the last chunk at this position.
+    pub fn pull_internal(&mut self, chunk: &[u8]) -> Result<Box<[u8]>, CryptoError> {
+        if self.finished {
+            return Err(CryptoError::InvalidStream("chunk after end of stream"));
+        }
+        let (plaintext, last) = match decrypt_xsalsa20_poly1305(
+            &*self.key,
+            &chunk_nonce(self.counter, CHUNK_FLAG_MORE),
+            chunk,
+        ) {
+            Ok(plaintext) => (plaintext, false),
+            Err(_) => (
+                decrypt_xsalsa20_poly1305(
+                    &*self.key,
+                    &chunk_nonce(self.counter, CHUNK_FLAG_FINAL),
+                    chunk,
+                )?,
+                true,
+            ),
+        };
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or(CryptoError::InvalidStream("too many chunks"))?;
+        self.finished = last;
+        Ok(plaintext)
+    }
+
+    /// Internal function to check that the last chunk has been decrypted.
+    pub fn end_internal(&self) -> Result<(), CryptoError> {
+        if self.finished {
+            Ok(())
+        } else {
+            Err(CryptoError::InvalidStream("stream truncated"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_SECRET: &str = "keySecret_z11111111111111111111111111111111";
+
+    fn encrypt_stream(chunks: &[&[u8]]) -> (Box<[u8]>, Vec<Box<[u8]>>) {
+        let mut encryptor = StreamEncryptor::new_internal(KEY_SECRET).unwrap();
+        let (last, rest) = chunks.split_last().unwrap();
+        let mut encrypted: Vec<Box<[u8]>> = rest
+            .iter()
+            .map(|chunk| encryptor.push_internal(chunk, false).unwrap())
+            .collect();
+        encrypted.push(encryptor.push_internal(last, true).unwrap());
+        (encryptor.header(), encrypted)
+    }
+
+    #[test]
+    fn test_stream_roundtrip() {
+        let chunks: [&[u8]; 4] = [b"first chunk", b"second", b"", b"last"];
+        let (header, encrypted) = encrypt_stream(&chunks);
+        assert_eq!(header.len(), STREAM_HEADER_LENGTH);
+
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header).unwrap();
+        for (i, (ciphertext, chunk)) in encrypted.iter().zip(chunks).enumerate() {
+            assert!(decryptor.end_internal().is_err());
+            assert_eq!(&*decryptor.pull_internal(ciphertext).unwrap(), chunk);
+            assert_eq!(decryptor.finished(), i == chunks.len() - 1);
+        }
+        decryptor.end_internal().unwrap();
+
+        // A single empty final chunk is a valid stream
+        let (header, encrypted) = encrypt_stream(&[b""]);
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header).unwrap();
+        assert!(decryptor.pull_internal(&encrypted[0]).unwrap().is_empty());
+        decryptor.end_internal().unwrap();
+    }
+
+    #[test]
+    fn test_stream_headers_differ() {
+        let (header_a, encrypted_a) = encrypt_stream(&[b"same"]);
+        let (header_b, encrypted_b) = encrypt_stream(&[b"same"]);
+        assert_ne!(header_a, header_b);
+        assert_ne!(encrypted_a, encrypted_b);
+
+        // Chunks from one stream can't be spliced into another
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header_a).unwrap();
+        assert!(decryptor.pull_internal(&encrypted_b[0]).is_err());
+    }
+
+    #[test]
+    fn test_stream_rejects_reordering_and_duplication() {
+        let (header, encrypted) = encrypt_stream(&[b"a", b"b", b"c"]);
+
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header).unwrap();
+        assert!(matches!(
+            decryptor.pull_internal(&encrypted[1]),
+            Err(CryptoError::WrongTag)
+        ));
+
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header).unwrap();
+        decryptor.pull_internal(&encrypted[0]).unwrap();
+        assert!(decryptor.pull_internal(&encrypted[0]).is_err());
+    }
+
+    #[test]
+    fn test_stream_rejects_truncation() {
+        let (header, encrypted) = encrypt_stream(&[b"a", b"b", b"c"]);
+
+        // Dropping the final chunk is detected at the end
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header).unwrap();
+        decryptor.pull_internal(&encrypted[0]).unwrap();
+        decryptor.pull_internal(&encrypted[1]).unwrap();
+        assert!(matches!(
+            decryptor.end_internal(),
+            Err(CryptoError::InvalidStream("stream truncated"))
+        ));
+
+        // Dropping a middle chunk is detected immediately
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &header).unwrap();
+        decryptor.pull_internal(&encrypted[0]).unwrap();
+        assert!(decryptor.pull_internal(&encrypted[2]).is_err());
+    }
+
+    #[test]
+    fn test_stream_rejects_chunks_after_end() {
+        let mut encryptor = StreamEncryptor::new_internal(KEY_SECRET).unwrap();
+        let last = encryptor.push_internal(b"only", true).unwrap();
+        assert!(matches!(
+            encryptor.push_internal(b"more", false),
+            Err(CryptoError::InvalidStream("stream already finished"))
+        ));
+
+        let mut decryptor = StreamDecryptor::new_internal(KEY_SECRET, &encryptor.header()).unwrap();
+        decryptor.pull_internal(&last).unwrap();
+        assert!(matches!(
+            decryptor.pull_internal(&last),
+            Err(CryptoError::InvalidStream("chunk after end of stream"))
+        ));
+    }
+
+    #[test]
+    fn test_stream_invalid_inputs() {
+        assert!(StreamEncryptor::new_internal("invalid_key").is_err());
+        assert!(StreamEncryptor::new_internal("keySecret_z1111").is_err());
+        assert!(matches!(
+            StreamDecryptor::new_internal(KEY_SECRET, &[0u8; 23]),
+            Err(CryptoError::InvalidLength("stream header", 24, 23))
+        ));
+
+        // Wrong key
+        let (header, encrypted) = encrypt_stream(&[b"secret"]);
+        let other_key = format!("keySecret_z{}", bs58::encode([9u8; 32]).into_string());
+        let mut decryptor = StreamDecryptor::new_internal(&other_key, &header).unwrap();
+        assert!(decryptor.pull_internal(&encrypted[0]).is_err());
+    }
+}
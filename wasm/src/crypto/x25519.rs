@@ -25,6 +25,7 @@ pub(crate) fn x25519_public_key_internal(private_key: &[u8]) -> Result<[u8; 32],
 
 /// WASM-exposed function to derive an X25519 public key from a private key.
 /// - `private_key`: 32 bytes of private key material
+///
 /// Returns 32 bytes of public key material or throws JsError if key is invalid.
 #[wasm_bindgen]
 pub fn x25519_public_key(private_key: &[u8]) -> Result<Vec<u8>, JsError> {
@@ -34,6 +35,8 @@ pub fn x25519_public_key(private_key: &[u8]) -> Result<Vec<u8>, JsError> {
 /// Internal function to perform X25519 Diffie-Hellman key exchange.
 /// Takes 32 bytes each of private and public key material.
 /// Returns 32 bytes of shared secret material or CryptoError if key lengths are invalid.
+/// Returns CryptoError::NonContributoryKeyExchange if the public key has low order,
+/// since the shared secret would then be known without the private key.
 pub(crate) fn x25519_diffie_hellman_internal(
     private_key: &[u8],
     public_key: &[u8],
@@ -46,12 +49,17 @@ pub(crate) fn x25519_diffie_hellman_internal(
         .map_err(|_| CryptoError::InvalidKeyLength(32, public_key.len()))?;
     let secret = StaticSecret::from(private_bytes);
     let public = PublicKey::from(public_bytes);
-    Ok(secret.diffie_hellman(&public).to_bytes())
+    let shared_secret = secret.diffie_hellman(&public);
+    if !shared_secret.was_contributory() {
+        return Err(CryptoError::NonContributoryKeyExchange);
+    }
+    Ok(shared_secret.to_bytes())
 }
 
 /// WASM-exposed function to perform X25519 Diffie-Hellman key exchange.
 /// - `private_key`: 32 bytes of private key material
 /// - `public_key`: 32 bytes of public key material
+///
 /// Returns 32 bytes of shared secret material or throws JsError if key exchange fails.
 #[wasm_bindgen]
 pub fn x25519_diffie_hellman(private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, JsError> {
@@ -79,6 +87,7 @@ pub fn get_sealer_id_internal(secret: &str) -> Result<String, CryptoError> {
 
 /// WASM-exposed function to derive a sealer ID from a sealer secret.
 /// - `secret`: Raw bytes of the sealer secret
+///
 /// Returns a base58-encoded sealer ID with "sealer_z" prefix or throws JsError if derivation fails.
 #[wasm_bindgen]
 pub fn get_sealer_id(secret: &[u8]) -> Result<String, JsError> {
@@ -137,6 +146,38 @@ mod tests {
         assert_ne!(shared_secret1, different_shared_secret);
     }
 
+    #[test]
+    fn test_x25519_rejects_low_order_points() {
+        // Points of order 1, 2, 4 and 8, and their non-canonical encodings
+        let small_order_points = [
+            // 0 (order 4)
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            // 1 (order 1)
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            // Order 8
+            "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800",
+            // Order 8
+            "5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157",
+            // p - 1 (order 2)
+            "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            // p, non-canonical 0
+            "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            // p + 1, non-canonical 1
+            "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            // Order 8 with the unused top bit set
+            "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b880",
+        ];
+
+        let private_key = new_x25519_private_key();
+        for point in small_order_points {
+            let public_key: [u8; 32] = hex::decode(point).unwrap().try_into().unwrap();
+            assert!(matches!(
+                x25519_diffie_hellman_internal(&private_key, &public_key),
+                Err(CryptoError::NonContributoryKeyExchange)
+            ));
+        }
+    }
+
     #[test]
     fn test_get_sealer_id() {
         // Create a test private key
@@ -12,6 +12,7 @@ use wasm_bindgen::prelude::*;
 /// - `key`: 32-byte key for encryption
 /// - `nonce_material`: Raw bytes used to generate a 24-byte nonce via BLAKE3
 /// - `plaintext`: Raw bytes to encrypt
+///
 /// Returns the encrypted bytes or throws a JsError if encryption fails.
 /// Note: This function does not provide authentication. Use encrypt_xsalsa20_poly1305 for authenticated encryption.
 #[wasm_bindgen]
@@ -21,13 +22,14 @@ pub fn encrypt_xsalsa20(
     plaintext: &[u8],
 ) -> Result<Box<[u8]>, JsError> {
     let nonce = generate_nonce(nonce_material);
-    Ok(encrypt_xsalsa20_raw_internal(key, &nonce, plaintext)?.into())
+    Ok(encrypt_xsalsa20_raw_internal(key, &nonce, plaintext)?)
 }
 
 /// WASM-exposed function for XSalsa20 decryption without authentication.
 /// - `key`: 32-byte key for decryption (must match encryption key)
 /// - `nonce_material`: Raw bytes used to generate a 24-byte nonce (must match encryption)
 /// - `ciphertext`: Encrypted bytes to decrypt
+///
 /// Returns the decrypted bytes or throws a JsError if decryption fails.
 /// Note: This function does not provide authentication. Use decrypt_xsalsa20_poly1305 for authenticated decryption.
 #[wasm_bindgen]
@@ -37,7 +39,7 @@ pub fn decrypt_xsalsa20(
     ciphertext: &[u8],
 ) -> Result<Box<[u8]>, JsError> {
     let nonce = generate_nonce(nonce_material);
-    Ok(decrypt_xsalsa20_raw_internal(key, &nonce, ciphertext)?.into())
+    Ok(decrypt_xsalsa20_raw_internal(key, &nonce, ciphertext)?)
 }
 
 /// Internal function for raw XSalsa20 encryption without nonce generation.
@@ -140,9 +142,107 @@ pub fn decrypt_xsalsa20_poly1305(
         .map_err(|_| CryptoError::WrongTag)
 }
 
+/// BLAKE3 derive_key context for XSalsa20-Poly1305 key commitments.
+const KEY_COMMITMENT_CONTEXT: &str = "jazz-crypto-rs 2025 XSalsa20-Poly1305 key commitment";
+
+/// Length of the key commitment prepended by the committing XSalsa20-Poly1305 mode.
+pub const KEY_COMMITMENT_LENGTH: usize = 32;
+
+/// Compute the BLAKE3 commitment to a key and nonce.
+/// The nonce is included so that ciphertexts under the same key can't be linked by their commitment.
+fn key_commitment(key: &[u8], nonce: &[u8]) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new_derive_key(KEY_COMMITMENT_CONTEXT);
+    hasher.update(key);
+    hasher.update(nonce);
+    hasher.finalize()
+}
+
+/// Key-committing XSalsa20-Poly1305 encryption.
+/// Poly1305 alone is not key-committing: a ciphertext can be crafted to authenticate under two keys.
+/// This prepends a 32-byte BLAKE3 commitment to the key and nonce, which decryption checks first.
+/// Returns the commitment followed by the regular XSalsa20-Poly1305 output.
+pub fn encrypt_xsalsa20_poly1305_committing_internal(
+    key: &[u8],
+    nonce: &[u8],
+    plaintext: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    let ciphertext = encrypt_xsalsa20_poly1305(key, nonce, plaintext)?;
+    let mut output = Vec::with_capacity(KEY_COMMITMENT_LENGTH + ciphertext.len());
+    output.extend_from_slice(key_commitment(key, nonce).as_bytes());
+    output.extend_from_slice(&ciphertext);
+    Ok(output.into_boxed_slice())
+}
+
+/// Key-committing XSalsa20-Poly1305 decryption.
+/// Returns CryptoError::KeyCommitmentMismatch if the ciphertext was not produced with this key and nonce,
+/// and CryptoError::WrongTag if authentication fails.
+pub fn decrypt_xsalsa20_poly1305_committing_internal(
+    key: &[u8],
+    nonce: &[u8],
+    ciphertext: &[u8],
+) -> Result<Box<[u8]>, CryptoError> {
+    if ciphertext.len() < KEY_COMMITMENT_LENGTH {
+        return Err(CryptoError::KeyCommitmentMismatch);
+    }
+    let (commitment, ciphertext) = ciphertext.split_at(KEY_COMMITMENT_LENGTH);
+    let commitment: [u8; KEY_COMMITMENT_LENGTH] = commitment.try_into().unwrap();
+
+    // Check key and nonce lengths before hashing them into the commitment
+    if key.len() != 32 {
+        return Err(CryptoError::InvalidKeyLength(32, key.len()));
+    }
+    if nonce.len() != 24 {
+        return Err(CryptoError::InvalidNonceLength);
+    }
+
+    // blake3::Hash comparisons are constant-time
+    if key_commitment(key, nonce) != commitment {
+        return Err(CryptoError::KeyCommitmentMismatch);
+    }
+    decrypt_xsalsa20_poly1305(key, nonce, ciphertext)
+}
+
+/// WASM-exposed function for key-committing XSalsa20-Poly1305 encryption.
+/// - `key`: 32-byte key for encryption
+/// - `nonce_material`: Raw bytes used to generate a 24-byte nonce via BLAKE3
+/// - `plaintext`: Raw bytes to encrypt
+///
+/// Returns a 32-byte key commitment followed by the ciphertext, or throws a JsError if encryption fails.
+#[wasm_bindgen]
+pub fn encrypt_xsalsa20_poly1305_committing(
+    key: &[u8],
+    nonce_material: &[u8],
+    plaintext: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    let nonce = generate_nonce(nonce_material);
+    Ok(encrypt_xsalsa20_poly1305_committing_internal(
+        key, &nonce, plaintext,
+    )?)
+}
+
+/// WASM-exposed function for key-committing XSalsa20-Poly1305 decryption.
+/// - `key`: 32-byte key for decryption (must match encryption key)
+/// - `nonce_material`: Raw bytes used to generate a 24-byte nonce (must match encryption)
+/// - `ciphertext`: Key commitment and encrypted bytes
+///
+/// Returns the decrypted bytes or throws a JsError if the commitment or tag doesn't match.
+#[wasm_bindgen]
+pub fn decrypt_xsalsa20_poly1305_committing(
+    key: &[u8],
+    nonce_material: &[u8],
+    ciphertext: &[u8],
+) -> Result<Box<[u8]>, JsError> {
+    let nonce = generate_nonce(nonce_material);
+    Ok(decrypt_xsalsa20_poly1305_committing_internal(
+        key, &nonce, ciphertext,
+    )?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_bigint::BigUint;
+    use num_traits::One;
 
     #[test]
     fn test_xsalsa20() {
@@ -253,4 +353,128 @@ mod tests {
         tampered[0] ^= 1;
         assert!(decrypt_xsalsa20_poly1305(&key, &nonce, &tampered).is_err());
     }
+
+    /// Poly1305 one-time key (r, s) that XSalsa20-Poly1305 derives from the first keystream block.
+    fn poly1305_key(key: &[u8; 32], nonce: &[u8; 24]) -> (BigUint, BigUint) {
+        let mut block = [0u8; 32];
+        let mut cipher = XSalsa20::new(key.into(), nonce.into());
+        cipher.apply_keystream(&mut block);
+        let clamp = BigUint::from_bytes_le(&[
+            0xff, 0xff, 0xff, 0x0f, 0xfc, 0xff, 0xff, 0x0f, 0xfc, 0xff, 0xff, 0x0f, 0xfc, 0xff,
+            0xff, 0x0f,
+        ]);
+        let r = BigUint::from_bytes_le(&block[..16]) & clamp;
+        let s = BigUint::from_bytes_le(&block[16..]);
+        (r, s)
+    }
+
+    /// Build a two-block ciphertext whose Poly1305 tag is valid under both keys.
+    /// For a 32-byte ciphertext with blocks c1, c2 (each with the 2^128 pad bit set),
+    /// Poly1305 computes (c1*r^2 + c2*r mod p + s) mod 2^128. Picking a common tag T gives
+    /// two linear equations in c1 and c2 modulo p = 2^130 - 5, which we solve directly,
+    /// retrying with a different T until both solutions are valid blocks.
+    fn two_key_collision(key1: &[u8; 32], key2: &[u8; 32], nonce: &[u8; 24]) -> Vec<u8> {
+        let p = (BigUint::one() << 130u32) - BigUint::from(5u32);
+        let two_128 = BigUint::one() << 128u32;
+        let (r1, s1) = poly1305_key(key1, nonce);
+        let (r2, s2) = poly1305_key(key2, nonce);
+        let inv = |x: &BigUint| x.modpow(&(&p - BigUint::from(2u32)), &p);
+        // Subtraction modulo p
+        let sub = |a: &BigUint, b: &BigUint| ((a + &p) - (b % &p)) % &p;
+
+        let det = sub(&(&r1 * &r1 % &p * &r2), &(&r2 * &r2 % &p * &r1));
+        let det_inv = inv(&det);
+
+        for tag in 0u32.. {
+            let tag = BigUint::from(tag);
+            // acc_k must equal (T - s_k) mod 2^128
+            let t1 = ((&tag + &two_128) - &s1) % &two_128;
+            let t2 = ((&tag + &two_128) - &s2) % &two_128;
+
+            let c1 = sub(&(&t1 * &r2), &(&t2 * &r1)) * &det_inv % &p;
+            let c2 = sub(&(&r1 * &r1 % &p * &t2), &(&r2 * &r2 % &p * &t1)) * &det_inv % &p;
+
+            let is_block = |c: &BigUint| *c >= two_128 && *c < &two_128 << 1u32;
+            if is_block(&c1) && is_block(&c2) {
+                let mut ciphertext = tag.to_bytes_le();
+                ciphertext.resize(16, 0);
+                for c in [c1, c2] {
+                    let mut block = (c - &two_128).to_bytes_le();
+                    block.resize(16, 0);
+                    ciphertext.extend_from_slice(&block);
+                }
+                return ciphertext;
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_committing_rejects_two_key_collision() {
+        let key1 = [1u8; 32];
+        let key2 = [2u8; 32];
+        let nonce = [3u8; 24];
+
+        // The forged ciphertext authenticates under both keys with plain XSalsa20-Poly1305...
+        let forged = two_key_collision(&key1, &key2, &nonce);
+        let plaintext1 = decrypt_xsalsa20_poly1305(&key1, &nonce, &forged).unwrap();
+        let plaintext2 = decrypt_xsalsa20_poly1305(&key2, &nonce, &forged).unwrap();
+        assert_ne!(plaintext1, plaintext2);
+
+        // ...but a commitment can only match one of them
+        let mut committed = key_commitment(&key1, &nonce).as_bytes().to_vec();
+        committed.extend_from_slice(&forged);
+        assert_eq!(
+            decrypt_xsalsa20_poly1305_committing_internal(&key1, &nonce, &committed).unwrap(),
+            plaintext1
+        );
+        assert!(matches!(
+            decrypt_xsalsa20_poly1305_committing_internal(&key2, &nonce, &committed),
+            Err(CryptoError::KeyCommitmentMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_xsalsa20_poly1305_committing() {
+        let key = [5u8; 32];
+        let nonce = [6u8; 24];
+        let plaintext = b"committed message";
+
+        let ciphertext =
+            encrypt_xsalsa20_poly1305_committing_internal(&key, &nonce, plaintext).unwrap();
+        assert_eq!(
+            ciphertext.len(),
+            KEY_COMMITMENT_LENGTH + 16 + plaintext.len()
+        );
+        let decrypted =
+            decrypt_xsalsa20_poly1305_committing_internal(&key, &nonce, &ciphertext).unwrap();
+        assert_eq!(&*decrypted, plaintext);
+
+        // Wrong key or nonce fails the commitment check
+        assert!(matches!(
+            decrypt_xsalsa20_poly1305_committing_internal(&[7u8; 32], &nonce, &ciphertext),
+            Err(CryptoError::KeyCommitmentMismatch)
+        ));
+        assert!(matches!(
+            decrypt_xsalsa20_poly1305_committing_internal(&key, &[7u8; 24], &ciphertext),
+            Err(CryptoError::KeyCommitmentMismatch)
+        ));
+
+        // Tampered body fails authentication
+        let mut tampered = ciphertext.to_vec();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            decrypt_xsalsa20_poly1305_committing_internal(&key, &nonce, &tampered),
+            Err(CryptoError::WrongTag)
+        ));
+
+        // Truncated input and bad lengths
+        assert!(
+            decrypt_xsalsa20_poly1305_committing_internal(&key, &nonce, &ciphertext[..31]).is_err()
+        );
+        assert!(matches!(
+            decrypt_xsalsa20_poly1305_committing_internal(&key[..31], &nonce, &ciphertext),
+            Err(CryptoError::InvalidKeyLength(32, 31))
+        ));
+    }
 }
@@ -8,10 +8,30 @@ pub enum CryptoError {
     InvalidSignatureLength,
     InvalidVerifyingKey(String),
     InvalidPublicKey(String),
+    NonContributoryKeyExchange,
     WrongTag,
+    KeyCommitmentMismatch,
     CipherError,
     InvalidPrefix(&'static str, &'static str),
     Base58Error(String),
+    Base64Error(String),
+    InvalidHasherState(&'static str),
+    InvalidBaoEncoding(&'static str),
+    HashMismatch,
+    InvalidJson(String),
+    InvalidLength(&'static str, usize, usize),
+    InvalidEnvelope(&'static str),
+    InvalidStream(&'static str),
+    InvalidKdfParams(String),
+    UnknownMnemonicWord(usize),
+    InvalidMnemonicChecksum,
+    WrongPassword,
+    CorruptExport(&'static str),
+    InvalidShareParams(&'static str),
+    InvalidShare(usize, &'static str),
+    NotEnoughShares(usize, usize),
+    ShareMismatch,
+    InvalidDerivationPath(&'static str),
 }
 
 impl fmt::Display for CryptoError {
@@ -30,12 +50,47 @@ impl fmt::Display for CryptoError {
             CryptoError::InvalidSignatureLength => write!(f, "Invalid signature length"),
             CryptoError::InvalidVerifyingKey(e) => write!(f, "Invalid verifying key: {}", e),
             CryptoError::InvalidPublicKey(e) => write!(f, "Invalid public key: {}", e),
+            CryptoError::NonContributoryKeyExchange => {
+                write!(f, "Key exchange is non-contributory (low-order public key)")
+            }
             CryptoError::WrongTag => write!(f, "Wrong tag"),
+            CryptoError::KeyCommitmentMismatch => write!(f, "Key commitment mismatch"),
             CryptoError::CipherError => write!(f, "Failed to create cipher"),
             CryptoError::InvalidPrefix(prefix, field) => {
                 write!(f, "Invalid {} format: must start with '{}'", field, prefix)
             }
             CryptoError::Base58Error(e) => write!(f, "Invalid base58: {}", e),
+            CryptoError::Base64Error(e) => write!(f, "Invalid base64: {}", e),
+            CryptoError::InvalidHasherState(e) => write!(f, "Invalid hasher state: {}", e),
+            CryptoError::InvalidBaoEncoding(e) => write!(f, "Invalid Bao encoding: {}", e),
+            CryptoError::HashMismatch => write!(f, "Hash mismatch"),
+            CryptoError::InvalidJson(e) => write!(f, "Invalid JSON: {}", e),
+            CryptoError::InvalidEnvelope(e) => write!(f, "Invalid envelope: {}", e),
+            CryptoError::InvalidStream(e) => write!(f, "Invalid stream: {}", e),
+            CryptoError::InvalidKdfParams(e) => write!(f, "Invalid KDF parameters: {}", e),
+            CryptoError::UnknownMnemonicWord(position) => {
+                write!(f, "Unknown word at position {position} of the mnemonic")
+            }
+            CryptoError::InvalidMnemonicChecksum => write!(f, "Invalid mnemonic checksum"),
+            CryptoError::WrongPassword => write!(f, "Wrong password"),
+            CryptoError::CorruptExport(e) => write!(f, "Corrupt export: {}", e),
+            CryptoError::InvalidShareParams(e) => write!(f, "Invalid sharing parameters: {}", e),
+            CryptoError::InvalidShare(position, e) => {
+                write!(f, "Invalid share at position {position}: {e}")
+            }
+            CryptoError::NotEnoughShares(needed, got) => {
+                write!(f, "Not enough shares (need {needed}, got {got})")
+            }
+            CryptoError::ShareMismatch => {
+                write!(f, "Shares do not combine to a consistent secret")
+            }
+            CryptoError::InvalidDerivationPath(e) => write!(f, "Invalid derivation path: {}", e),
+            CryptoError::InvalidLength(field, expected, actual) => {
+                write!(
+                    f,
+                    "Invalid {field} length (expected {expected}, got {actual})"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,599 @@
+use crate::error::CryptoError;
+use blake3::hazmat::{
+    left_subtree_len, merge_subtrees_non_root, merge_subtrees_root, ChainingValue, HasherExt, Mode,
+};
+use blake3::CHUNK_LEN;
+use wasm_bindgen::prelude::*;
+
+/// Size of the little-endian content length header at the start of outboards and slices.
+const HEADER_LEN: usize = 8;
+/// Size of an encoded parent node (left chaining value followed by right chaining value).
+const PARENT_LEN: usize = 2 * blake3::OUT_LEN;
+
+/// Number of chunks in a subtree of `content_len` bytes (an empty input is still one chunk).
+fn count_chunks(content_len: u64) -> u64 {
+    content_len.div_ceil(CHUNK_LEN as u64).max(1)
+}
+
+/// Number of bytes the parent nodes of a subtree of `content_len` bytes occupy in pre-order.
+fn encoded_parents_len(content_len: u64) -> u64 {
+    (count_chunks(content_len) - 1) * PARENT_LEN as u64
+}
+
+/// Compute the non-root chaining value of the chunk starting at byte `offset`.
+fn chunk_cv(offset: u64, chunk: &[u8]) -> ChainingValue {
+    blake3::Hasher::new()
+        .set_input_offset(offset)
+        .update(chunk)
+        .finalize_non_root()
+}
+
+/// Check that a parent node hashes to the expected value, as a root node if `expected` is None.
+fn parent_matches(
+    parent: &[u8],
+    root_hash: &blake3::Hash,
+    expected: Option<&ChainingValue>,
+) -> (bool, ChainingValue, ChainingValue) {
+    let left: ChainingValue = parent[..blake3::OUT_LEN].try_into().unwrap();
+    let right: ChainingValue = parent[blake3::OUT_LEN..PARENT_LEN].try_into().unwrap();
+    let matches = match expected {
+        None => merge_subtrees_root(&left, &right, Mode::Hash) == *root_hash,
+        Some(cv) => merge_subtrees_non_root(&left, &right, Mode::Hash) == *cv,
+    };
+    (matches, left, right)
+}
+
+fn parse_root_hash(root_hash: &[u8]) -> Result<blake3::Hash, CryptoError> {
+    blake3::Hash::from_slice(root_hash)
+        .map_err(|_| CryptoError::InvalidKeyLength(blake3::OUT_LEN, root_hash.len()))
+}
+
+fn parse_header(encoded: &[u8]) -> Result<u64, CryptoError> {
+    let (header, _) = encoded
+        .split_first_chunk::<HEADER_LEN>()
+        .ok_or(CryptoError::InvalidBaoEncoding("missing length header"))?;
+    Ok(u64::from_le_bytes(*header))
+}
+
+/// Normalize a requested byte range to the chunks a slice must contain.
+/// At least one chunk is always included; a start past the end selects the final chunk,
+/// which is what authenticates the content length.
+fn slice_chunk_range(content_len: u64, slice_start: u64, slice_len: u64) -> (u64, u64) {
+    let start = slice_start.min(content_len.saturating_sub(1));
+    let end = start
+        .saturating_add(slice_len.max(1))
+        .min(content_len.max(1));
+    (start, end)
+}
+
+fn encode_outboard_subtree(data: &[u8], offset: u64, outboard: &mut Vec<u8>) -> ChainingValue {
+    if data.len() <= CHUNK_LEN {
+        return chunk_cv(offset, data);
+    }
+    // Parents are written in pre-order, so reserve this node before descending
+    let position = outboard.len();
+    outboard.extend_from_slice(&[0u8; PARENT_LEN]);
+    let left_len = left_subtree_len(data.len() as u64) as usize;
+    let left = encode_outboard_subtree(&data[..left_len], offset, outboard);
+    let right = encode_outboard_subtree(&data[left_len..], offset + left_len as u64, outboard);
+    outboard[position..position + blake3::OUT_LEN].copy_from_slice(&left);
+    outboard[position + blake3::OUT_LEN..position + PARENT_LEN].copy_from_slice(&right);
+    merge_subtrees_non_root(&left, &right, Mode::Hash)
+}
+
+/// Internal function to compute the Bao outboard encoding of some content.
+/// - `data`: The content to encode
+///
+/// Returns the outboard: an 8-byte little-endian content length followed by every parent
+/// node of the BLAKE3 tree in pre-order. The root hash of the tree is `blake3_hash_once(data)`.
+pub fn bao_encode_outboard_internal(data: &[u8]) -> Vec<u8> {
+    let mut outboard =
+        Vec::with_capacity(HEADER_LEN + encoded_parents_len(data.len() as u64) as usize);
+    outboard.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    if data.len() > CHUNK_LEN {
+        encode_outboard_subtree(data, 0, &mut outboard);
+    }
+    outboard
+}
+
+/// Internal function to verify a single chunk against a root hash using an outboard encoding.
+/// - `root_hash`: 32-byte BLAKE3 hash of the whole content
+/// - `outboard`: Outboard encoding from `bao_encode_outboard_internal` (not trusted)
+/// - `chunk_index`: Index of the 1024-byte chunk being verified
+/// - `chunk`: The chunk bytes (only the final chunk may be shorter than 1024 bytes)
+///
+/// Returns true if the chunk and every parent node on its path are consistent with the root hash,
+/// false otherwise, or CryptoError if the outboard is malformed or the index is out of range.
+///
+/// Note that the content length in the outboard header is only authenticated once the final
+/// chunk has been verified.
+pub fn bao_verify_chunk_internal(
+    root_hash: &[u8],
+    outboard: &[u8],
+    chunk_index: u64,
+    chunk: &[u8],
+) -> Result<bool, CryptoError> {
+    let root_hash = parse_root_hash(root_hash)?;
+    let content_len = parse_header(outboard)?;
+    if (outboard.len() - HEADER_LEN) as u64 != encoded_parents_len(content_len) {
+        return Err(CryptoError::InvalidBaoEncoding(
+            "outboard length does not match header",
+        ));
+    }
+    if chunk_index >= count_chunks(content_len) {
+        return Err(CryptoError::InvalidBaoEncoding("chunk index out of range"));
+    }
+
+    let chunk_start = chunk_index * CHUNK_LEN as u64;
+    if chunk.len() as u64 != (content_len - chunk_start).min(CHUNK_LEN as u64) {
+        return Ok(false);
+    }
+    if content_len <= CHUNK_LEN as u64 {
+        return Ok(blake3::hash(chunk) == root_hash);
+    }
+
+    // Walk from the root down to the chunk, checking each parent node on the way
+    let mut expected: Option<ChainingValue> = None;
+    let mut subtree_start = 0u64;
+    let mut subtree_len = content_len;
+    let mut cursor = HEADER_LEN;
+    while subtree_len > CHUNK_LEN as u64 {
+        let parent = &outboard[cursor..cursor + PARENT_LEN];
+        let (matches, left, right) = parent_matches(parent, &root_hash, expected.as_ref());
+        if !matches {
+            return Ok(false);
+        }
+        let left_len = left_subtree_len(subtree_len);
+        if chunk_start < subtree_start + left_len {
+            expected = Some(left);
+            subtree_len = left_len;
+            cursor += PARENT_LEN;
+        } else {
+            expected = Some(right);
+            subtree_start += left_len;
+            subtree_len -= left_len;
+            cursor += PARENT_LEN + encoded_parents_len(left_len) as usize;
+        }
+    }
+    Ok(expected == Some(chunk_cv(chunk_start, chunk)))
+}
+
+fn extract_slice_subtree(
+    data: &[u8],
+    outboard: &[u8],
+    subtree_start: u64,
+    subtree_len: u64,
+    cursor: usize,
+    range: (u64, u64),
+    slice: &mut Vec<u8>,
+) {
+    if subtree_len <= CHUNK_LEN as u64 {
+        slice.extend_from_slice(
+            &data[subtree_start as usize..(subtree_start + subtree_len) as usize],
+        );
+        return;
+    }
+    slice.extend_from_slice(&outboard[cursor..cursor + PARENT_LEN]);
+    let left_len = left_subtree_len(subtree_len);
+    if range.0 < subtree_start + left_len {
+        extract_slice_subtree(
+            data,
+            outboard,
+            subtree_start,
+            left_len,
+            cursor + PARENT_LEN,
+            range,
+            slice,
+        );
+    }
+    if range.1 > subtree_start + left_len {
+        extract_slice_subtree(
+            data,
+            outboard,
+            subtree_start + left_len,
+            subtree_len - left_len,
+            cursor + PARENT_LEN + encoded_parents_len(left_len) as usize,
+            range,
+            slice,
+        );
+    }
+}
+
+/// Internal function to extract a verifiable slice of content.
+/// - `data`: The full content
+/// - `outboard`: Outboard encoding of `data`
+/// - `slice_start`: Byte offset where the requested range starts
+/// - `slice_len`: Length of the requested range in bytes
+///
+/// Returns a slice containing the length header, the parent nodes and the chunks needed to
+/// verify the range, in pre-order, or CryptoError if the outboard does not match the data.
+pub fn bao_extract_slice_internal(
+    data: &[u8],
+    outboard: &[u8],
+    slice_start: u64,
+    slice_len: u64,
+) -> Result<Vec<u8>, CryptoError> {
+    let content_len = parse_header(outboard)?;
+    if content_len != data.len() as u64
+        || (outboard.len() - HEADER_LEN) as u64 != encoded_parents_len(content_len)
+    {
+        return Err(CryptoError::InvalidBaoEncoding(
+            "outboard does not match content length",
+        ));
+    }
+
+    let mut slice = outboard[..HEADER_LEN].to_vec();
+    let range = slice_chunk_range(content_len, slice_start, slice_len);
+    extract_slice_subtree(
+        data,
+        outboard,
+        0,
+        content_len,
+        HEADER_LEN,
+        range,
+        &mut slice,
+    );
+    Ok(slice)
+}
+
+struct SliceDecoder<'a> {
+    slice: &'a [u8],
+    cursor: usize,
+    root_hash: blake3::Hash,
+    range: (u64, u64),
+    output: Vec<u8>,
+}
+
+impl<'a> SliceDecoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CryptoError> {
+        let slice: &'a [u8] = self.slice;
+        let bytes = slice
+            .get(self.cursor..self.cursor + len)
+            .ok_or(CryptoError::InvalidBaoEncoding("truncated slice"))?;
+        self.cursor += len;
+        Ok(bytes)
+    }
+
+    fn decode_subtree(
+        &mut self,
+        subtree_start: u64,
+        subtree_len: u64,
+        expected: Option<ChainingValue>,
+    ) -> Result<(), CryptoError> {
+        if subtree_len <= CHUNK_LEN as u64 {
+            let chunk = self.take(subtree_len as usize)?;
+            let matches = match expected {
+                None => blake3::hash(chunk) == self.root_hash,
+                Some(cv) => chunk_cv(subtree_start, chunk) == cv,
+            };
+            if !matches {
+                return Err(CryptoError::HashMismatch);
+            }
+            self.output.extend_from_slice(chunk);
+            return Ok(());
+        }
+
+        let parent = self.take(PARENT_LEN)?;
+        let (matches, left, right) = parent_matches(parent, &self.root_hash, expected.as_ref());
+        if !matches {
+            return Err(CryptoError::HashMismatch);
+        }
+        let left_len = left_subtree_len(subtree_len);
+        if self.range.0 < subtree_start + left_len {
+            self.decode_subtree(subtree_start, left_len, Some(left))?;
+        }
+        if self.range.1 > subtree_start + left_len {
+            self.decode_subtree(
+                subtree_start + left_len,
+                subtree_len - left_len,
+                Some(right),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Internal function to verify and decode a slice produced by `bao_extract_slice_internal`.
+/// - `slice`: The slice bytes (not trusted)
+/// - `root_hash`: 32-byte BLAKE3 hash of the whole content
+/// - `slice_start`: Byte offset the slice was extracted at
+/// - `slice_len`: Length of the range the slice was extracted for
+///
+/// Returns the verified content bytes in the requested range (clipped to the content length),
+/// CryptoError::HashMismatch if any node or chunk fails verification,
+/// or CryptoError::InvalidBaoEncoding if the slice is malformed.
+pub fn bao_decode_slice_internal(
+    slice: &[u8],
+    root_hash: &[u8],
+    slice_start: u64,
+    slice_len: u64,
+) -> Result<Vec<u8>, CryptoError> {
+    let root_hash = parse_root_hash(root_hash)?;
+    let content_len = parse_header(slice)?;
+    let range = slice_chunk_range(content_len, slice_start, slice_len);
+
+    let mut decoder = SliceDecoder {
+        slice,
+        cursor: HEADER_LEN,
+        root_hash,
+        range,
+        output: Vec::new(),
+    };
+    decoder.decode_subtree(0, content_len, None)?;
+    if decoder.cursor != slice.len() {
+        return Err(CryptoError::InvalidBaoEncoding("trailing bytes in slice"));
+    }
+
+    // The decoded chunks cover whole chunks around the range; return just the range itself
+    let chunks_start = range.0 - range.0 % CHUNK_LEN as u64;
+    let start = slice_start.min(content_len);
+    let end = slice_start.saturating_add(slice_len).min(content_len);
+    Ok(decoder.output[(start - chunks_start) as usize..(end - chunks_start) as usize].to_vec())
+}
+
+/// WASM-exposed function to compute the Bao outboard encoding of some content.
+/// - `data`: The content to encode
+///
+/// Returns the outboard bytes (8-byte length header followed by the tree's parent nodes).
+/// The matching root hash is `blake3_hash_once(data)`.
+#[wasm_bindgen]
+pub fn bao_encode_outboard(data: &[u8]) -> Box<[u8]> {
+    bao_encode_outboard_internal(data).into_boxed_slice()
+}
+
+/// WASM-exposed function to extract a verifiable slice of content.
+/// - `data`: The full content
+/// - `outboard`: Outboard encoding of `data`
+/// - `slice_start`: Byte offset where the requested range starts
+/// - `slice_len`: Length of the requested range in bytes
+///
+/// Returns the slice bytes or throws JsError if the outboard does not match the data.
+#[wasm_bindgen]
+pub fn bao_extract_slice(
+    data: &[u8],
+    outboard: &[u8],
+    slice_start: u64,
+    slice_len: u64,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(bao_extract_slice_internal(data, outboard, slice_start, slice_len)?.into_boxed_slice())
+}
+
+/// WASM-exposed function to verify and decode a slice.
+/// - `slice`: The slice bytes received from a peer
+/// - `root_hash`: 32-byte BLAKE3 hash of the whole content
+/// - `slice_start`: Byte offset the slice was extracted at
+/// - `slice_len`: Length of the range the slice was extracted for
+///
+/// Returns the verified content bytes or throws JsError if verification fails.
+#[wasm_bindgen]
+pub fn bao_decode_slice(
+    slice: &[u8],
+    root_hash: &[u8],
+    slice_start: u64,
+    slice_len: u64,
+) -> Result<Box<[u8]>, JsError> {
+    Ok(bao_decode_slice_internal(slice, root_hash, slice_start, slice_len)?.into_boxed_slice())
+}
+
+/// Verifier for the chunks of a single piece of content, for checking chunks as they arrive.
+/// Holds the root hash and outboard so they only cross the WASM boundary once.
+#[wasm_bindgen]
+pub struct BaoVerifier {
+    root_hash: [u8; 32],
+    outboard: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl BaoVerifier {
+    /// Create a verifier from a trusted root hash and an (untrusted) outboard encoding.
+    /// Throws JsError if the root hash or outboard is malformed.
+    #[wasm_bindgen(constructor)]
+    pub fn new(root_hash: &[u8], outboard: &[u8]) -> Result<BaoVerifier, JsError> {
+        let root_hash = parse_root_hash(root_hash)?;
+        let content_len = parse_header(outboard)?;
+        if (outboard.len() - HEADER_LEN) as u64 != encoded_parents_len(content_len) {
+            return Err(
+                CryptoError::InvalidBaoEncoding("outboard length does not match header").into(),
+            );
+        }
+        Ok(BaoVerifier {
+            root_hash: *root_hash.as_bytes(),
+            outboard: outboard.to_vec(),
+        })
+    }
+
+    /// Content length claimed by the outboard header.
+    pub fn content_len(&self) -> u64 {
+        u64::from_le_bytes(self.outboard[..HEADER_LEN].try_into().unwrap())
+    }
+
+    /// Number of 1024-byte chunks in the content.
+    pub fn chunk_count(&self) -> u64 {
+        count_chunks(self.content_len())
+    }
+
+    /// Verify one chunk of the content.
+    /// Returns true if the chunk is authentic at `chunk_index`, false otherwise,
+    /// or throws JsError if the index is out of range.
+    pub fn verify_chunk(&self, chunk_index: u64, chunk: &[u8]) -> Result<bool, JsError> {
+        Ok(bao_verify_chunk_internal(
+            &self.root_hash,
+            &self.outboard,
+            chunk_index,
+            chunk,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len as u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    const LENGTHS: [usize; 9] = [0, 1, 1024, 1025, 2048, 3000, 4096, 5000, 20_000];
+
+    #[test]
+    fn test_bao_outboard_and_verify_chunk() {
+        for len in LENGTHS {
+            let data = test_data(len);
+            let root_hash = blake3::hash(&data);
+            let outboard = bao_encode_outboard_internal(&data);
+            assert_eq!(
+                outboard.len(),
+                HEADER_LEN + encoded_parents_len(len as u64) as usize
+            );
+
+            // Every chunk verifies at its own index
+            let chunks: Vec<&[u8]> = if data.is_empty() {
+                vec![&[]]
+            } else {
+                data.chunks(CHUNK_LEN).collect()
+            };
+            for (index, chunk) in chunks.iter().enumerate() {
+                assert!(
+                    bao_verify_chunk_internal(root_hash.as_bytes(), &outboard, index as u64, chunk)
+                        .unwrap(),
+                    "len {len}, chunk {index}"
+                );
+            }
+
+            // Corrupted chunks are rejected
+            if let Some(first) = chunks.first().filter(|c| !c.is_empty()) {
+                let mut corrupted = first.to_vec();
+                corrupted[0] ^= 1;
+                assert!(
+                    !bao_verify_chunk_internal(root_hash.as_bytes(), &outboard, 0, &corrupted)
+                        .unwrap()
+                );
+            }
+
+            // A chunk is not valid at another index
+            if chunks.len() > 2 {
+                assert!(
+                    !bao_verify_chunk_internal(root_hash.as_bytes(), &outboard, 1, chunks[0])
+                        .unwrap()
+                );
+            }
+
+            // Out-of-range indices are errors
+            assert!(bao_verify_chunk_internal(
+                root_hash.as_bytes(),
+                &outboard,
+                chunks.len() as u64,
+                &[]
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_bao_corrupted_outboard() {
+        let data = test_data(5000);
+        let root_hash = blake3::hash(&data);
+        let outboard = bao_encode_outboard_internal(&data);
+
+        // Flipping a bit in any parent node breaks verification of the chunks below it
+        for position in (HEADER_LEN..outboard.len()).step_by(PARENT_LEN / 2) {
+            let mut corrupted = outboard.clone();
+            corrupted[position] ^= 1;
+            let any_rejected = data.chunks(CHUNK_LEN).enumerate().any(|(index, chunk)| {
+                !bao_verify_chunk_internal(root_hash.as_bytes(), &corrupted, index as u64, chunk)
+                    .unwrap()
+            });
+            assert!(any_rejected, "position {position}");
+        }
+
+        // Wrong root hash
+        let wrong_root = blake3::hash(b"something else");
+        assert!(
+            !bao_verify_chunk_internal(wrong_root.as_bytes(), &outboard, 0, &data[..1024]).unwrap()
+        );
+
+        // Truncated outboard
+        assert!(bao_verify_chunk_internal(
+            root_hash.as_bytes(),
+            &outboard[..outboard.len() - 1],
+            0,
+            &data[..1024]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_bao_slices() {
+        for len in LENGTHS {
+            let data = test_data(len);
+            let root_hash = blake3::hash(&data);
+            let outboard = bao_encode_outboard_internal(&data);
+
+            for (start, slice_len) in [
+                (0, 0),
+                (0, 10),
+                (1000, 100),
+                (1024, 1024),
+                (2500, 3000),
+                (0, len as u64),
+                (len as u64, 10),
+                (len as u64 + 5000, 10),
+            ] {
+                let slice = bao_extract_slice_internal(&data, &outboard, start, slice_len).unwrap();
+                let decoded =
+                    bao_decode_slice_internal(&slice, root_hash.as_bytes(), start, slice_len)
+                        .unwrap();
+                let expected_start = (start as usize).min(len);
+                let expected_end = (start.saturating_add(slice_len) as usize).min(len);
+                assert_eq!(
+                    decoded,
+                    &data[expected_start..expected_end],
+                    "len {len}, start {start}, slice_len {slice_len}"
+                );
+
+                // Any corruption of the slice is detected
+                for position in [HEADER_LEN, slice.len() / 2, slice.len() - 1] {
+                    if position >= slice.len() || position < HEADER_LEN {
+                        continue;
+                    }
+                    let mut corrupted = slice.clone();
+                    corrupted[position] ^= 1;
+                    assert!(bao_decode_slice_internal(
+                        &corrupted,
+                        root_hash.as_bytes(),
+                        start,
+                        slice_len
+                    )
+                    .is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bao_slice_is_smaller_than_content() {
+        let data = test_data(64 * 1024);
+        let outboard = bao_encode_outboard_internal(&data);
+        let slice = bao_extract_slice_internal(&data, &outboard, 10_000, 100).unwrap();
+
+        // One chunk plus one parent node per tree level
+        assert_eq!(slice.len(), HEADER_LEN + 6 * PARENT_LEN + CHUNK_LEN);
+    }
+
+    #[test]
+    fn test_bao_verifier() {
+        let data = test_data(3000);
+        let root_hash = blake3::hash(&data);
+        let outboard = bao_encode_outboard_internal(&data);
+
+        let verifier = BaoVerifier::new(root_hash.as_bytes(), &outboard)
+            .ok()
+            .unwrap();
+        assert_eq!(verifier.content_len(), 3000);
+        assert_eq!(verifier.chunk_count(), 3);
+        for (index, chunk) in data.chunks(CHUNK_LEN).enumerate() {
+            assert!(verifier.verify_chunk(index as u64, chunk).ok().unwrap());
+        }
+        assert!(!verifier.verify_chunk(2, &data[..952]).ok().unwrap());
+    }
+}
@@ -1,7 +1,19 @@
+use crate::error::CryptoError;
+use crate::json::stable_stringify::{
+    stable_stringify_internal, stable_stringify_js_internal, stable_stringify_str_internal,
+};
+use crate::prefix::{encode_prefixed, HASH_PREFIX, SHORT_HASH_PREFIX};
+use blake3::hazmat::{
+    max_subtree_len, merge_subtrees_non_root, merge_subtrees_root_xof, ChainingValue, HasherExt,
+    Mode,
+};
+use blake3::CHUNK_LEN;
+use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
 /// Generate a 24-byte nonce from input material using BLAKE3.
 /// - `nonce_material`: Raw bytes to derive the nonce from
+///
 /// Returns 24 bytes suitable for use as a nonce in cryptographic operations.
 /// This function is deterministic - the same input will produce the same nonce.
 #[wasm_bindgen]
@@ -11,8 +23,23 @@ pub fn generate_nonce(nonce_material: &[u8]) -> Box<[u8]> {
     hasher.finalize().as_bytes()[..24].into()
 }
 
+/// Build cojson's nonce material for a private transaction.
+/// - `co_id`: ID of the CoValue the transaction belongs to
+/// - `session_id`: ID of the session that made the transaction
+/// - `tx_index`: Index of the transaction within the session
+///
+/// Returns the stable-stringified `{"in":co_id,"tx":{"sessionID":session_id,"txIndex":tx_index}}`.
+pub fn transaction_nonce_material(co_id: &str, session_id: &str, tx_index: u32) -> Vec<u8> {
+    let material = serde_json::json!({
+        "in": co_id,
+        "tx": { "sessionID": session_id, "txIndex": tx_index },
+    });
+    stable_stringify_internal(&material).into_bytes()
+}
+
 /// Hash data once using BLAKE3.
 /// - `data`: Raw bytes to hash
+///
 /// Returns 32 bytes of hash output.
 /// This is the simplest way to compute a BLAKE3 hash of a single piece of data.
 #[wasm_bindgen]
@@ -25,6 +52,7 @@ pub fn blake3_hash_once(data: &[u8]) -> Box<[u8]> {
 /// Hash data once using BLAKE3 with a context prefix.
 /// - `data`: Raw bytes to hash
 /// - `context`: Context bytes to prefix to the data
+///
 /// Returns 32 bytes of hash output.
 /// This is useful for domain separation - the same data hashed with different contexts will produce different outputs.
 #[wasm_bindgen]
@@ -35,27 +63,323 @@ pub fn blake3_hash_once_with_context(data: &[u8], context: &[u8]) -> Box<[u8]> {
     hasher.finalize().as_bytes().to_vec().into_boxed_slice()
 }
 
+/// Number of BLAKE3 output bytes kept in a short hash, matching cojson's `shortHashLength`.
+pub const SHORT_HASH_LENGTH: usize = 19;
+
+/// Hash data into cojson's "hash_z" format using BLAKE3.
+/// - `data`: Raw bytes to hash
+///
+/// Returns "hash_z" followed by the base58-encoded 32-byte BLAKE3 hash.
+#[wasm_bindgen]
+pub fn secure_hash(data: &[u8]) -> String {
+    encode_prefixed(HASH_PREFIX, blake3::hash(data).as_bytes())
+}
+
+/// Hash data into cojson's "shortHash_z" format using BLAKE3.
+/// - `data`: Raw bytes to hash
+///
+/// Returns "shortHash_z" followed by the base58-encoded first 19 bytes of the BLAKE3 hash.
+/// Like `generate_nonce`, this truncates the regular 32-byte output.
+#[wasm_bindgen]
+pub fn short_hash(data: &[u8]) -> String {
+    encode_prefixed(
+        SHORT_HASH_PREFIX,
+        &blake3::hash(data).as_bytes()[..SHORT_HASH_LENGTH],
+    )
+}
+
+/// Internal function to compute cojson's `secureHash` of a JSON value.
+/// - `value`: The JSON value to hash
+///
+/// Returns "hash_z" followed by the base58-encoded BLAKE3 hash of the value's canonical JSON.
+pub fn secure_hash_json_internal(value: &Value) -> String {
+    secure_hash(stable_stringify_internal(value).as_bytes())
+}
+
+/// WASM-exposed function to compute cojson's `secureHash` of a JavaScript value.
+/// - `value`: Any JSON-compatible JavaScript value
+///
+/// Returns "hash_z" followed by the base58-encoded BLAKE3 hash of the value's canonical JSON,
+/// or throws JsError if the value cannot be serialized.
+#[wasm_bindgen]
+pub fn secure_hash_json(value: JsValue) -> Result<String, JsError> {
+    Ok(secure_hash(
+        stable_stringify_js_internal(&value)?.as_bytes(),
+    ))
+}
+
+/// WASM-exposed function to compute cojson's `secureHash` of JSON text.
+/// - `json`: JSON text in any formatting and key order
+///
+/// Returns "hash_z" followed by the base58-encoded BLAKE3 hash of the canonical JSON,
+/// or throws JsError if the text is not valid JSON.
+#[wasm_bindgen]
+pub fn secure_hash_json_str(json: &str) -> Result<String, JsError> {
+    Ok(secure_hash(stable_stringify_str_internal(json)?.as_bytes()))
+}
+
+/// Version byte written at the start of every exported Blake3Hasher state.
+const STATE_VERSION: u8 = 1;
+
+/// Largest chunk index BLAKE3 can address, since input offsets are limited to 2^64 bytes.
+const MAX_CHUNK_COUNTER: u64 = u64::MAX / CHUNK_LEN as u64;
+
+/// Incremental BLAKE3 hasher whose state can be exported and resumed later.
+///
+/// Instead of wrapping `blake3::Hasher` (whose internals are opaque), this keeps the
+/// BLAKE3 tree state explicitly: the chaining values of completed subtrees along the
+/// left edge of the tree, plus the bytes of the rightmost chunk. Complete subtrees are
+/// still hashed by `blake3::Hasher` through the `hazmat` API, so large updates keep
+/// their SIMD speed and the output is identical to a one-shot hash.
 #[wasm_bindgen]
-pub struct Blake3Hasher(blake3::Hasher);
+#[derive(Clone, Default)]
+pub struct Blake3Hasher {
+    /// Chaining values of completed subtrees, leftmost (largest) first.
+    cv_stack: Vec<ChainingValue>,
+    /// Index of the chunk currently buffered in `chunk`.
+    chunk_counter: u64,
+    /// Bytes of the rightmost chunk. This is never more than CHUNK_LEN bytes, and is
+    /// only empty when nothing has been hashed yet.
+    chunk: Vec<u8>,
+}
 
 #[wasm_bindgen]
 impl Blake3Hasher {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Blake3Hasher(blake3::Hasher::new())
+        Blake3Hasher {
+            cv_stack: Vec::new(),
+            chunk_counter: 0,
+            chunk: Vec::with_capacity(CHUNK_LEN),
+        }
     }
 
-    pub fn update(&mut self, data: &[u8]) {
-        self.0.update(data);
+    pub fn update(&mut self, mut data: &[u8]) {
+        // Top up the buffered chunk. A full chunk stays buffered until more input
+        // arrives, because the last chunk might still turn out to be the root.
+        if !self.chunk.is_empty() {
+            let take = (CHUNK_LEN - self.chunk.len()).min(data.len());
+            self.chunk.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if data.is_empty() {
+                return;
+            }
+            let cv = subtree_cv(self.chunk_counter, &self.chunk);
+            self.push_cv(cv, self.chunk_counter);
+            self.advance_chunk_counter(1);
+            self.chunk.clear();
+        }
+
+        // Hash whole subtrees directly, always leaving at least one byte for the final chunk.
+        while data.len() > CHUNK_LEN {
+            let mut subtree_len = 1usize << (usize::BITS - 1 - (data.len() - 1).leading_zeros());
+            if let Some(max_len) = max_subtree_len(self.chunk_counter * CHUNK_LEN as u64) {
+                subtree_len = subtree_len.min(max_len as usize);
+            }
+            let cv = subtree_cv(self.chunk_counter, &data[..subtree_len]);
+            self.push_cv(cv, self.chunk_counter);
+            self.advance_chunk_counter((subtree_len / CHUNK_LEN) as u64);
+            data = &data[subtree_len..];
+        }
+
+        self.chunk.extend_from_slice(data);
+        self.merge_cv_stack(self.chunk_counter);
     }
 
     pub fn finalize(&self) -> Box<[u8]> {
-        self.0.finalize().as_bytes().to_vec().into_boxed_slice()
+        self.finalize_xof(blake3::OUT_LEN)
+    }
+
+    /// Finalize the hash with an arbitrary output length (BLAKE3 extendable output).
+    /// - `len`: Number of output bytes to produce
+    ///
+    /// Returns `len` bytes of hash output. The first 32 bytes are identical to `finalize`.
+    /// Like `finalize`, this does not consume the state, so more data can be added afterwards.
+    pub fn finalize_xof(&self, len: usize) -> Box<[u8]> {
+        let mut output = vec![0u8; len];
+        self.root_output().fill(&mut output);
+        output.into_boxed_slice()
     }
 
+    /// Get a seekable reader over the extended output of the current state.
+    /// Returns a Blake3OutputReader positioned at offset 0.
+    pub fn finalize_xof_reader(&self) -> Blake3OutputReader {
+        Blake3OutputReader(self.root_output())
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn clone(&self) -> Self {
-        // The blake3::Hasher type implements Clone
-        Blake3Hasher(self.0.clone())
+        Clone::clone(self)
+    }
+
+    /// Serialize the hasher state so hashing can be resumed later with `import_state`.
+    /// Returns a versioned byte encoding of the chaining-value stack and the buffered chunk:
+    /// - 1 byte: format version (currently 1)
+    /// - 8 bytes: index of the buffered chunk, little-endian
+    /// - 1 byte: number of chaining values, followed by 32 bytes for each, leftmost first
+    /// - 2 bytes: length of the buffered chunk, little-endian, followed by its bytes
+    pub fn export_state(&self) -> Box<[u8]> {
+        let mut state =
+            Vec::with_capacity(12 + self.cv_stack.len() * blake3::OUT_LEN + self.chunk.len());
+        state.push(STATE_VERSION);
+        state.extend_from_slice(&self.chunk_counter.to_le_bytes());
+        state.push(self.cv_stack.len() as u8);
+        for cv in &self.cv_stack {
+            state.extend_from_slice(cv);
+        }
+        state.extend_from_slice(&(self.chunk.len() as u16).to_le_bytes());
+        state.extend_from_slice(&self.chunk);
+        state.into_boxed_slice()
+    }
+
+    /// Restore a hasher from bytes produced by `export_state`.
+    /// - `state`: The exported state bytes
+    ///
+    /// Returns a Blake3Hasher that continues exactly where the exported one stopped,
+    /// or throws JsError if the state is malformed or uses an unknown version.
+    pub fn import_state(state: &[u8]) -> Result<Blake3Hasher, JsError> {
+        Ok(Self::import_state_internal(state)?)
+    }
+}
+
+impl Blake3Hasher {
+    /// Internal function to restore a hasher from bytes produced by `export_state`.
+    /// Returns CryptoError::InvalidHasherState if the state is malformed or inconsistent.
+    pub fn import_state_internal(state: &[u8]) -> Result<Self, CryptoError> {
+        let (&version, rest) = state
+            .split_first()
+            .ok_or(CryptoError::InvalidHasherState("empty state"))?;
+        if version != STATE_VERSION {
+            return Err(CryptoError::InvalidHasherState("unsupported version"));
+        }
+
+        let (counter, rest) = rest
+            .split_first_chunk::<8>()
+            .ok_or(CryptoError::InvalidHasherState("truncated chunk counter"))?;
+        let chunk_counter = u64::from_le_bytes(*counter);
+        if chunk_counter > MAX_CHUNK_COUNTER {
+            return Err(CryptoError::InvalidHasherState(
+                "chunk counter out of range",
+            ));
+        }
+
+        let (&stack_len, mut rest) = rest
+            .split_first()
+            .ok_or(CryptoError::InvalidHasherState("truncated stack length"))?;
+        // Between updates the stack is fully merged, so it holds one chaining value
+        // per set bit of the chunk counter.
+        if stack_len as u32 != chunk_counter.count_ones() {
+            return Err(CryptoError::InvalidHasherState(
+                "stack length does not match chunk counter",
+            ));
+        }
+        let mut cv_stack = Vec::with_capacity(stack_len as usize);
+        for _ in 0..stack_len {
+            let (cv, tail) = rest
+                .split_first_chunk::<{ blake3::OUT_LEN }>()
+                .ok_or(CryptoError::InvalidHasherState("truncated chaining value"))?;
+            cv_stack.push(*cv);
+            rest = tail;
+        }
+
+        let (chunk_len, chunk) = rest
+            .split_first_chunk::<2>()
+            .ok_or(CryptoError::InvalidHasherState("truncated chunk length"))?;
+        let chunk_len = u16::from_le_bytes(*chunk_len) as usize;
+        if chunk_len > CHUNK_LEN {
+            return Err(CryptoError::InvalidHasherState("chunk too long"));
+        }
+        if chunk.len() != chunk_len {
+            return Err(CryptoError::InvalidHasherState(
+                "chunk length does not match data",
+            ));
+        }
+        if chunk_len == 0 && chunk_counter != 0 {
+            return Err(CryptoError::InvalidHasherState("missing final chunk"));
+        }
+
+        let mut buffered = Vec::with_capacity(CHUNK_LEN);
+        buffered.extend_from_slice(chunk);
+        Ok(Blake3Hasher {
+            cv_stack,
+            chunk_counter,
+            chunk: buffered,
+        })
+    }
+
+    /// Merge completed subtrees until the stack matches the binary representation of
+    /// `total_chunks`, the same lazy merging rule the reference implementation uses.
+    fn merge_cv_stack(&mut self, total_chunks: u64) {
+        let post_merge_len = total_chunks.count_ones() as usize;
+        while self.cv_stack.len() > post_merge_len {
+            let right = self.cv_stack.pop().unwrap();
+            let left = self.cv_stack.pop().unwrap();
+            self.cv_stack
+                .push(merge_subtrees_non_root(&left, &right, Mode::Hash));
+        }
+    }
+
+    /// Move past `chunks` completed chunks.
+    /// Panics if the input would grow past BLAKE3's 2^64-byte limit, rather than wrapping.
+    fn advance_chunk_counter(&mut self, chunks: u64) {
+        self.chunk_counter = self
+            .chunk_counter
+            .checked_add(chunks)
+            .filter(|&counter| counter <= MAX_CHUNK_COUNTER)
+            .expect("BLAKE3 input longer than 2^64 bytes");
+    }
+
+    fn push_cv(&mut self, cv: ChainingValue, chunk_counter: u64) {
+        self.merge_cv_stack(chunk_counter);
+        self.cv_stack.push(cv);
+    }
+
+    /// Fold the stack and the buffered chunk into the root node of the tree.
+    fn root_output(&self) -> blake3::OutputReader {
+        let Some((first, rest)) = self.cv_stack.split_first() else {
+            // A single chunk is the root itself
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&self.chunk);
+            return hasher.finalize_xof();
+        };
+        let mut right = subtree_cv(self.chunk_counter, &self.chunk);
+        for left in rest.iter().rev() {
+            right = merge_subtrees_non_root(left, &right, Mode::Hash);
+        }
+        merge_subtrees_root_xof(first, &right, Mode::Hash)
+    }
+}
+
+/// Compute the non-root chaining value of a chunk or subtree starting at `chunk_counter`.
+fn subtree_cv(chunk_counter: u64, data: &[u8]) -> ChainingValue {
+    blake3::Hasher::new()
+        .set_input_offset(chunk_counter * CHUNK_LEN as u64)
+        .update(data)
+        .finalize_non_root()
+}
+
+/// Reader over the extendable output of a finalized BLAKE3 state.
+/// The output stream is effectively unbounded (up to 2^64 bytes) and can be read from any offset.
+#[wasm_bindgen]
+pub struct Blake3OutputReader(blake3::OutputReader);
+
+#[wasm_bindgen]
+impl Blake3OutputReader {
+    /// Read the next `len` bytes of output and advance the position by `len`.
+    pub fn read(&mut self, len: usize) -> Box<[u8]> {
+        let mut output = vec![0u8; len];
+        self.0.fill(&mut output);
+        output.into_boxed_slice()
+    }
+
+    /// Get the current byte offset into the output stream.
+    pub fn position(&self) -> u64 {
+        self.0.position()
+    }
+
+    /// Seek to an absolute byte offset in the output stream.
+    pub fn set_position(&mut self, position: u64) {
+        self.0.set_position(position);
     }
 }
 
@@ -69,6 +393,7 @@ pub fn blake3_empty_state() -> Blake3Hasher {
 /// Update a BLAKE3 state with new data for incremental hashing.
 /// - `state`: Current Blake3Hasher instance
 /// - `data`: New data to incorporate into the hash
+///
 /// Returns the updated Blake3Hasher.
 #[wasm_bindgen]
 pub fn blake3_update_state(state: &mut Blake3Hasher, data: &[u8]) {
@@ -77,6 +402,7 @@ pub fn blake3_update_state(state: &mut Blake3Hasher, data: &[u8]) {
 
 /// Get the final hash from a BLAKE3 state.
 /// - `state`: The Blake3Hasher to finalize
+///
 /// Returns 32 bytes of hash output.
 /// This finalizes an incremental hashing operation.
 #[wasm_bindgen]
@@ -84,6 +410,16 @@ pub fn blake3_digest_for_state(state: Blake3Hasher) -> Box<[u8]> {
     state.finalize()
 }
 
+/// Get an extended-length digest from a BLAKE3 state.
+/// - `state`: The Blake3Hasher to finalize
+/// - `len`: Number of output bytes to produce
+///
+/// Returns `len` bytes of hash output, whose first 32 bytes match `blake3_digest_for_state`.
+#[wasm_bindgen]
+pub fn blake3_xof_digest_for_state(state: Blake3Hasher, len: usize) -> Box<[u8]> {
+    state.finalize_xof(len)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +551,236 @@ mod tests {
             "Final state should match expected hash"
         );
     }
+
+    #[test]
+    fn test_blake3_xof() {
+        let mut state = blake3_empty_state();
+        blake3_update_state(&mut state, b"test input");
+
+        // The first 32 bytes of extended output equal the regular digest
+        let xof = state.finalize_xof(100);
+        assert_eq!(xof.len(), 100);
+        assert_eq!(&xof[..32], &*state.finalize());
+
+        // Shorter outputs are prefixes of longer ones
+        let short = state.finalize_xof(24);
+        assert_eq!(&*short, &xof[..24]);
+
+        // Matches the reference implementation
+        let mut expected = [0u8; 100];
+        blake3::Hasher::new()
+            .update(b"test input")
+            .finalize_xof()
+            .fill(&mut expected);
+        assert_eq!(&*xof, &expected[..]);
+
+        // Zero-length output is allowed
+        assert!(state.finalize_xof(0).is_empty());
+
+        // Free function matches the method
+        assert_eq!(blake3_xof_digest_for_state(state.clone(), 100), xof);
+    }
+
+    #[test]
+    fn test_blake3_output_reader() {
+        let mut state = blake3_empty_state();
+        blake3_update_state(&mut state, b"test input");
+        let xof = state.finalize_xof(256);
+
+        // Sequential reads continue where the previous one stopped
+        let mut reader = state.finalize_xof_reader();
+        assert_eq!(reader.position(), 0);
+        let first = reader.read(100);
+        let second = reader.read(156);
+        assert_eq!(reader.position(), 256);
+        assert_eq!(&*first, &xof[..100]);
+        assert_eq!(&*second, &xof[100..]);
+
+        // Seeking reads from an arbitrary offset, including mid-block
+        reader.set_position(70);
+        assert_eq!(&*reader.read(50), &xof[70..120]);
+
+        // Seeking far ahead still matches the reference implementation
+        let mut expected = [0u8; 16];
+        let mut reference = blake3::Hasher::new().update(b"test input").finalize_xof();
+        reference.set_position(1 << 40);
+        reference.fill(&mut expected);
+        reader.set_position(1 << 40);
+        assert_eq!(&*reader.read(16), &expected[..]);
+    }
+
+    fn reference_hash(data: &[u8]) -> Box<[u8]> {
+        let mut expected = [0u8; 64];
+        blake3::Hasher::new()
+            .update(data)
+            .finalize_xof()
+            .fill(&mut expected);
+        expected.into()
+    }
+
+    #[test]
+    fn test_blake3_incremental_matches_reference() {
+        let data: Vec<u8> = (0..40_000u32).map(|i| (i % 251) as u8).collect();
+
+        // Feed the data in uneven pieces that cross chunk and subtree boundaries
+        for step in [1, 7, 1023, 1024, 1025, 3000, 8192, 40_000] {
+            let mut state = blake3_empty_state();
+            for piece in data.chunks(step) {
+                blake3_update_state(&mut state, piece);
+            }
+            assert_eq!(state.finalize_xof(64), reference_hash(&data), "step {step}");
+        }
+
+        // Lengths at and around chunk boundaries, hashed in a single update
+        for len in [0, 1, 1023, 1024, 1025, 2048, 2049, 4096, 5000, 16384, 16385] {
+            let mut state = blake3_empty_state();
+            blake3_update_state(&mut state, &data[..len]);
+            assert_eq!(
+                state.finalize_xof(64),
+                reference_hash(&data[..len]),
+                "len {len}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_blake3_export_import_state() {
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 253) as u8).collect();
+
+        for split in [0, 1, 1024, 1025, 3072, 3073, 10_000, 20_000] {
+            let mut state = blake3_empty_state();
+            blake3_update_state(&mut state, &data[..split]);
+            let exported = state.export_state();
+
+            // The restored state produces the same digest
+            let restored = Blake3Hasher::import_state_internal(&exported).unwrap();
+            assert_eq!(restored.finalize(), state.finalize());
+
+            // Re-exporting is stable
+            assert_eq!(restored.export_state(), exported);
+
+            // Hashing can continue after the restore
+            let mut restored = restored;
+            blake3_update_state(&mut restored, &data[split..]);
+            assert_eq!(
+                restored.finalize_xof(64),
+                reference_hash(&data),
+                "split {split}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_blake3_import_state_errors() {
+        let mut state = blake3_empty_state();
+        blake3_update_state(&mut state, &[7u8; 3000]);
+        let exported = state.export_state();
+
+        // Empty state
+        assert!(matches!(
+            Blake3Hasher::import_state_internal(&[]),
+            Err(CryptoError::InvalidHasherState(_))
+        ));
+
+        // Unknown version
+        let mut wrong_version = exported.to_vec();
+        wrong_version[0] = 2;
+        assert!(Blake3Hasher::import_state_internal(&wrong_version).is_err());
+
+        // Stack length inconsistent with the chunk counter
+        let mut wrong_counter = exported.to_vec();
+        wrong_counter[1] = 3;
+        assert!(Blake3Hasher::import_state_internal(&wrong_counter).is_err());
+
+        // Truncated or extended data
+        assert!(Blake3Hasher::import_state_internal(&exported[..exported.len() - 1]).is_err());
+        let mut extended = exported.to_vec();
+        extended.push(0);
+        assert!(Blake3Hasher::import_state_internal(&extended).is_err());
+
+        // A non-empty tree must have a buffered final chunk
+        let mut missing_chunk = exported[..exported.len() - (3000 - 2048)].to_vec();
+        let len = missing_chunk.len();
+        missing_chunk[len - 2..].copy_from_slice(&0u16.to_le_bytes());
+        assert!(Blake3Hasher::import_state_internal(&missing_chunk).is_err());
+
+        // Chunk counters past BLAKE3's 2^64-byte limit are rejected instead of overflowing
+        let state_with_counter = |counter: u64| {
+            let mut state = vec![1u8];
+            state.extend_from_slice(&counter.to_le_bytes());
+            state.push(counter.count_ones() as u8);
+            for _ in 0..counter.count_ones() {
+                state.extend_from_slice(&[9u8; 32]);
+            }
+            state.extend_from_slice(&1u16.to_le_bytes());
+            state.push(0);
+            state
+        };
+        assert!(matches!(
+            Blake3Hasher::import_state_internal(&state_with_counter(1 << 60)),
+            Err(CryptoError::InvalidHasherState(
+                "chunk counter out of range"
+            ))
+        ));
+        assert!(matches!(
+            Blake3Hasher::import_state_internal(&state_with_counter(MAX_CHUNK_COUNTER + 1)),
+            Err(CryptoError::InvalidHasherState(
+                "chunk counter out of range"
+            ))
+        ));
+        let last_chunk =
+            Blake3Hasher::import_state_internal(&state_with_counter(MAX_CHUNK_COUNTER)).unwrap();
+        assert_eq!(last_chunk.finalize().len(), 32);
+    }
+
+    #[test]
+    fn test_secure_hash_json() {
+        let value: Value = serde_json::from_str(r#"{"b":[1,2],"a":"x"}"#).unwrap();
+        let hash = secure_hash_json_internal(&value);
+        assert!(hash.starts_with("hash_z"));
+
+        // The hash covers the canonical JSON bytes
+        let expected = blake3_hash_once(br#"{"a":"x","b":[1,2]}"#);
+        assert_eq!(
+            bs58::decode(hash.strip_prefix("hash_z").unwrap())
+                .into_vec()
+                .unwrap(),
+            expected.to_vec()
+        );
+
+        // Equivalent JSON text hashes the same regardless of formatting
+        let from_text = secure_hash(
+            stable_stringify_str_internal("{ \"a\": \"x\", \"b\": [1.0, 2] }")
+                .unwrap()
+                .as_bytes(),
+        );
+        assert_eq!(from_text, hash);
+    }
+
+    #[test]
+    fn test_secure_and_short_hash() {
+        use crate::prefix::{parse_hash_internal, parse_short_hash_internal};
+
+        let data = b"test input";
+        let hash = secure_hash(data);
+        assert!(hash.starts_with("hash_z"));
+        assert_eq!(
+            parse_hash_internal(&hash).unwrap().to_vec(),
+            blake3_hash_once(data).to_vec()
+        );
+
+        // The short hash is a prefix of the full hash
+        let short = short_hash(data);
+        assert!(short.starts_with("shortHash_z"));
+        let short_bytes = parse_short_hash_internal(&short).unwrap();
+        assert_eq!(short_bytes.len(), SHORT_HASH_LENGTH);
+        assert_eq!(
+            &short_bytes[..],
+            &blake3_hash_once(data)[..SHORT_HASH_LENGTH]
+        );
+
+        // Different input produces different hashes
+        assert_ne!(secure_hash(b"different input"), hash);
+        assert_ne!(short_hash(b"different input"), short);
+    }
 }
@@ -0,0 +1,386 @@
+use crate::error::CryptoError;
+use js_sys::{Array, Function, Object, Reflect};
+use serde_json::{Map, Number, Value};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Internal function to serialize a JSON value exactly like cojson's `stableStringify`.
+/// - `value`: The JSON value to serialize
+///
+/// Returns the canonical JSON text: object keys sorted by UTF-16 code units, no whitespace,
+/// numbers formatted like JavaScript's `Number.prototype.toString`, and strings escaped
+/// like `JSON.stringify`. Strings starting with "encrypted_U" or "binary_U" are emitted
+/// without escaping, as cojson does.
+pub fn stable_stringify_internal(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(value, &mut out);
+    out
+}
+
+/// Internal function to parse JSON text into a value for canonical serialization.
+/// Numbers are read as IEEE 754 doubles, the same way `JSON.parse` reads them.
+/// Returns CryptoError::InvalidJson if the text is not valid JSON.
+pub fn parse_json_internal(json: &str) -> Result<Value, CryptoError> {
+    serde_json::from_str(json).map_err(|e| CryptoError::InvalidJson(e.to_string()))
+}
+
+/// Internal function to serialize JSON text canonically, ignoring its original formatting.
+/// Returns CryptoError::InvalidJson if the text is not valid JSON.
+pub fn stable_stringify_str_internal(json: &str) -> Result<String, CryptoError> {
+    Ok(stable_stringify_internal(&parse_json_internal(json)?))
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(true) => out.push_str("true"),
+        Value::Bool(false) => out.push_str("false"),
+        Value::Number(n) => write_number(n.as_f64().unwrap_or(f64::NAN), out),
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // JavaScript sorts keys by UTF-16 code units, which differs from
+            // Rust's UTF-8 byte order for characters outside the BMP
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_by(|a, b| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_escaped_string(key, out);
+                out.push(':');
+                write_value(&map[key], out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(s: &str, out: &mut String) {
+    if s.starts_with("encrypted_U") || s.starts_with("binary_U") {
+        out.push('"');
+        out.push_str(s);
+        out.push('"');
+    } else {
+        write_escaped_string(s, out);
+    }
+}
+
+/// Escape a string the way `JSON.stringify` does.
+fn write_escaped_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Format a number the way JavaScript's `Number.prototype.toString` does.
+/// Non-finite numbers become `null`, as in `JSON.stringify`.
+fn write_number(n: f64, out: &mut String) {
+    if !n.is_finite() {
+        out.push_str("null");
+        return;
+    }
+    if n == 0.0 {
+        // Covers -0, which JavaScript prints as "0"
+        out.push('0');
+        return;
+    }
+    if n < 0.0 {
+        out.push('-');
+    }
+
+    // Rust's exponential formatting yields the shortest round-tripping digits,
+    // which is also what JavaScript uses; only the layout rules differ.
+    let scientific = format!("{:e}", n.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let point = exponent.parse::<i32>().unwrap() + 1;
+
+    if k <= point && point <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (point - k) as usize));
+    } else if 0 < point && point <= 21 {
+        out.push_str(&digits[..point as usize]);
+        out.push('.');
+        out.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', -point as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if point > 0 { '+' } else { '-' });
+        out.push_str(&(point - 1).abs().to_string());
+    }
+}
+
+/// Internal function to convert a JavaScript value into a JSON value, following the
+/// same rules as cojson's `stableStringify`: `toJSON` is honoured, `undefined`, functions
+/// and symbols are dropped from objects (and become `null` in arrays), and non-finite
+/// numbers become `null`.
+/// Returns None for values that have no JSON representation, or CryptoError::InvalidJson
+/// for values that `JSON.stringify` would reject (such as BigInt or circular structures).
+pub fn js_value_to_json(value: &JsValue) -> Result<Option<Value>, CryptoError> {
+    js_value_to_json_inner(value, &mut Vec::new())
+}
+
+/// Convert a JavaScript value, with `seen` holding the arrays and objects currently being
+/// converted, so a value that contains itself fails like `JSON.stringify` instead of
+/// recursing until the stack overflows.
+fn js_value_to_json_inner(
+    value: &JsValue,
+    seen: &mut Vec<JsValue>,
+) -> Result<Option<Value>, CryptoError> {
+    let mut value = value.clone();
+    if value.is_object() {
+        let to_json = Reflect::get(&value, &JsValue::from_str("toJSON"))
+            .map_err(|_| CryptoError::InvalidJson("failed to read toJSON".to_string()))?;
+        if let Some(to_json) = to_json.dyn_ref::<Function>() {
+            value = to_json
+                .call0(&value)
+                .map_err(|_| CryptoError::InvalidJson("toJSON threw".to_string()))?;
+        }
+    }
+
+    if value.is_undefined() || value.is_function() || value.is_symbol() {
+        return Ok(None);
+    }
+    if value.is_null() {
+        return Ok(Some(Value::Null));
+    }
+    if let Some(b) = value.as_bool() {
+        return Ok(Some(Value::Bool(b)));
+    }
+    if let Some(n) = value.as_f64() {
+        return Ok(Some(Number::from_f64(n).map_or(Value::Null, Value::Number)));
+    }
+    if let Some(s) = value.as_string() {
+        return Ok(Some(Value::String(s)));
+    }
+    if value.is_bigint() {
+        return Err(CryptoError::InvalidJson(
+            "BigInt values cannot be serialized".to_string(),
+        ));
+    }
+
+    if seen.iter().any(|ancestor| Object::is(ancestor, &value)) {
+        return Err(CryptoError::InvalidJson("circular structure".to_string()));
+    }
+    seen.push(value.clone());
+    let result = if Array::is_array(&value) {
+        Array::from(&value)
+            .iter()
+            .map(|item| Ok(js_value_to_json_inner(&item, seen)?.unwrap_or(Value::Null)))
+            .collect::<Result<Vec<_>, CryptoError>>()
+            .map(Value::Array)
+    } else {
+        js_object_to_json(value.unchecked_ref(), seen).map(Value::Object)
+    };
+    seen.pop();
+    result.map(Some)
+}
+
+fn js_object_to_json(
+    object: &Object,
+    seen: &mut Vec<JsValue>,
+) -> Result<Map<String, Value>, CryptoError> {
+    let mut map = Map::new();
+    for key in Object::keys(object).iter() {
+        let field = Reflect::get(object, &key)
+            .map_err(|_| CryptoError::InvalidJson("failed to read property".to_string()))?;
+        if let (Some(key), Some(field)) = (key.as_string(), js_value_to_json_inner(&field, seen)?) {
+            map.insert(key, field);
+        }
+    }
+    Ok(map)
+}
+
+/// Internal function to serialize a JavaScript value and fail if it has no JSON representation.
+pub(crate) fn stable_stringify_js_internal(value: &JsValue) -> Result<String, CryptoError> {
+    let value = js_value_to_json(value)?.ok_or(CryptoError::InvalidJson(
+        "value has no JSON representation".to_string(),
+    ))?;
+    Ok(stable_stringify_internal(&value))
+}
+
+/// WASM-exposed function to serialize a JavaScript value exactly like cojson's `stableStringify`.
+/// - `value`: Any JSON-compatible JavaScript value
+///
+/// Returns the canonical JSON string, undefined if the value has no JSON representation,
+/// or throws JsError if the value cannot be serialized (such as BigInt).
+#[wasm_bindgen]
+pub fn stable_stringify(value: JsValue) -> Result<Option<String>, JsError> {
+    Ok(js_value_to_json(&value)?.map(|value| stable_stringify_internal(&value)))
+}
+
+/// WASM-exposed function to re-serialize JSON text canonically.
+/// - `json`: JSON text in any formatting and key order
+///
+/// Returns the canonical JSON string or throws JsError if the text is not valid JSON.
+#[wasm_bindgen]
+pub fn stable_stringify_str(json: &str) -> Result<String, JsError> {
+    Ok(stable_stringify_str_internal(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn number(n: f64) -> String {
+        stable_stringify_internal(&Value::Number(Number::from_f64(n).unwrap()))
+    }
+
+    #[test]
+    fn test_stable_stringify_structure() {
+        let value = json!({"b": 1, "a": [1, "x", null, true, false], "c": {"z": {}, "y": []}});
+        assert_eq!(
+            stable_stringify_internal(&value),
+            r#"{"a":[1,"x",null,true,false],"b":1,"c":{"y":[],"z":{}}}"#
+        );
+
+        // Formatting and key order of the input do not matter
+        let reordered = stable_stringify_str_internal(
+            r#"{ "c": { "y": [ ], "z": { } }, "b": 1.0, "a": [1, "x", null, true, false] }"#,
+        )
+        .unwrap();
+        assert_eq!(reordered, stable_stringify_internal(&value));
+    }
+
+    #[test]
+    fn test_stable_stringify_numbers() {
+        assert_eq!(number(0.0), "0");
+        assert_eq!(number(-0.0), "0");
+        assert_eq!(number(100.0), "100");
+        assert_eq!(number(-42.0), "-42");
+        assert_eq!(number(1.5), "1.5");
+        assert_eq!(number(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(number(0.000001), "0.000001");
+        assert_eq!(number(1e-7), "1e-7");
+        assert_eq!(number(-1.5e-7), "-1.5e-7");
+        assert_eq!(number(1e20), "100000000000000000000");
+        assert_eq!(number(1e21), "1e+21");
+        assert_eq!(number(123456789012345680000.0), "123456789012345680000");
+        assert_eq!(number(1.7976931348623157e308), "1.7976931348623157e+308");
+        assert_eq!(number(5e-324), "5e-324");
+        assert_eq!(number(9007199254740993.0), "9007199254740992");
+
+        // Integers outside the double range are rounded like JSON.parse does
+        assert_eq!(
+            stable_stringify_str_internal("[12345678901234567890, 1E3, 2.50]").unwrap(),
+            "[12345678901234567000,1000,2.5]"
+        );
+    }
+
+    #[test]
+    fn test_stable_stringify_strings() {
+        assert_eq!(
+            stable_stringify_internal(&json!(
+                "quote\" backslash\\ slash/ \u{1}\u{8}\u{c}\n\r\t\u{1f}"
+            )),
+            r#""quote\" backslash\\ slash/ \u0001\b\f\n\r\t\u001f""#
+        );
+
+        // Non-ASCII characters are not escaped
+        assert_eq!(
+            stable_stringify_internal(&json!("é😀\u{2028}")),
+            "\"é😀\u{2028}\""
+        );
+
+        // Encrypted and binary values are passed through verbatim
+        assert_eq!(
+            stable_stringify_internal(&json!("encrypted_Uab\"c")),
+            "\"encrypted_Uab\"c\""
+        );
+        assert_eq!(
+            stable_stringify_internal(&json!("binary_U\n")),
+            "\"binary_U\n\""
+        );
+    }
+
+    #[test]
+    fn test_stable_stringify_key_order() {
+        // UTF-16 order puts the surrogate pair of U+1F600 (0xD83D) before U+FF61,
+        // while UTF-8 byte order would put it after
+        let value = json!({"\u{ff61}": 1, "\u{1f600}": 2, "B": 3, "a": 4, "": 5});
+        assert_eq!(
+            stable_stringify_internal(&value),
+            "{\"\":5,\"B\":3,\"a\":4,\"\u{1f600}\":2,\"\u{ff61}\":1}"
+        );
+    }
+
+    #[test]
+    fn test_stable_stringify_transaction() {
+        // Shape of a cojson private transaction
+        let tx = stable_stringify_str_internal(
+            r#"{"privacy":"private","madeAt":1720000000000,"keyUsed":"key_zabc","encryptedChanges":"encrypted_UxYz-_="}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            tx,
+            r#"{"encryptedChanges":"encrypted_UxYz-_=","keyUsed":"key_zabc","madeAt":1720000000000,"privacy":"private"}"#
+        );
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert!(matches!(
+            stable_stringify_str_internal("{\"a\":"),
+            Err(CryptoError::InvalidJson(_))
+        ));
+        assert!(stable_stringify_str_internal("").is_err());
+        assert!(stable_stringify_str_internal("NaN").is_err());
+    }
+
+    // JavaScript values only exist on wasm, so this runs under wasm-bindgen-test
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn test_js_value_to_json_rejects_cycles() {
+        let object = Object::new();
+        let array = Array::new();
+        array.push(&object);
+        Reflect::set(&object, &"list".into(), &array).unwrap();
+        assert!(matches!(
+            js_value_to_json(&object),
+            Err(CryptoError::InvalidJson(message)) if message == "circular structure"
+        ));
+        assert!(js_value_to_json(&array).is_err());
+
+        // The same object reached twice without a cycle is fine
+        let leaf = Object::new();
+        Reflect::set(&leaf, &"n".into(), &1.into()).unwrap();
+        let shared = Array::of2(&leaf, &leaf);
+        assert_eq!(
+            stable_stringify_js_internal(&shared).unwrap(),
+            r#"[{"n":1},{"n":1}]"#
+        );
+    }
+}
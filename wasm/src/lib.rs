@@ -3,23 +3,54 @@ use wasm_bindgen::prelude::*;
 mod error;
 pub use error::CryptoError;
 
+pub mod prefix;
+
 pub mod hash {
+    pub mod bao;
     pub mod blake3;
+    pub use bao::*;
     pub use blake3::*;
 }
 
+pub mod json {
+    pub mod stable_stringify;
+    pub use stable_stringify::*;
+}
+
 pub mod crypto {
+    pub mod agent;
+    pub mod backup;
     pub mod ed25519;
     pub mod encrypt;
+    pub mod envelope;
+    pub mod hd;
+    pub mod hybrid;
+    pub mod kdf;
+    #[cfg(feature = "ml-dsa")]
+    pub mod ml_dsa;
+    pub mod mnemonic;
+    pub mod recovery;
     pub mod seal;
     pub mod sign;
+    pub mod stream;
     pub mod x25519;
     pub mod xsalsa20;
 
+    pub use agent::*;
+    pub use backup::*;
     pub use ed25519::*;
     pub use encrypt::*;
+    pub use envelope::*;
+    pub use hd::*;
+    pub use hybrid::*;
+    pub use kdf::*;
+    #[cfg(feature = "ml-dsa")]
+    pub use ml_dsa::*;
+    pub use mnemonic::*;
+    pub use recovery::*;
     pub use seal::*;
     pub use sign::*;
+    pub use stream::*;
     pub use x25519::*;
     pub use xsalsa20::*;
 }
@@ -0,0 +1,186 @@
+use crate::error::CryptoError;
+use crate::hash::blake3::SHORT_HASH_LENGTH;
+use base64::engine::general_purpose::URL_SAFE;
+use base64::Engine;
+use bs58;
+use wasm_bindgen::prelude::*;
+
+pub const HASH_PREFIX: &str = "hash_z";
+pub const SHORT_HASH_PREFIX: &str = "shortHash_z";
+pub const KEY_ID_PREFIX: &str = "key_z";
+pub const ENCRYPTED_PREFIX: &str = "encrypted_U";
+pub const SEALER_ID_PREFIX: &str = "sealer_z";
+pub const SIGNER_ID_PREFIX: &str = "signer_z";
+pub const SEALER_SECRET_PREFIX: &str = "sealerSecret_z";
+pub const SIGNER_SECRET_PREFIX: &str = "signerSecret_z";
+pub const ML_DSA_SIGNER_SECRET_PREFIX: &str = "mlDsaSignerSecret_z";
+pub const ML_DSA_SIGNER_ID_PREFIX: &str = "mlDsaSigner_z";
+pub const ML_DSA_SIGNATURE_PREFIX: &str = "mlDsaSignature_z";
+pub const HYBRID_SEALER_SECRET_PREFIX: &str = "hybridSealerSecret_z";
+pub const HYBRID_SEALER_ID_PREFIX: &str = "hybridSealer_z";
+pub const SHARE_PREFIX: &str = "share_z";
+
+/// Encode raw bytes as a prefixed base58 string, such as "hash_z...".
+pub fn encode_prefixed(prefix: &str, bytes: &[u8]) -> String {
+    format!("{}{}", prefix, bs58::encode(bytes).into_string())
+}
+
+/// Decode a prefixed base58 string back into raw bytes.
+/// - `value`: The prefixed string
+/// - `prefix`: The expected prefix, including the trailing "z"
+/// - `field`: Human-readable name of the value, used in error messages
+///
+/// Returns the decoded bytes or CryptoError if the prefix or base58 encoding is invalid.
+pub fn decode_prefixed(
+    value: &str,
+    prefix: &'static str,
+    field: &'static str,
+) -> Result<Vec<u8>, CryptoError> {
+    let encoded = value
+        .strip_prefix(prefix)
+        .ok_or(CryptoError::InvalidPrefix(prefix, field))?;
+    bs58::decode(encoded)
+        .into_vec()
+        .map_err(|e| CryptoError::Base58Error(e.to_string()))
+}
+
+/// Decode a prefixed base58 string that must contain exactly `N` bytes.
+/// Returns CryptoError::InvalidLength if the decoded length is wrong.
+pub fn decode_prefixed_array<const N: usize>(
+    value: &str,
+    prefix: &'static str,
+    field: &'static str,
+) -> Result<[u8; N], CryptoError> {
+    let bytes = decode_prefixed(value, prefix, field)?;
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| CryptoError::InvalidLength(field, N, bytes.len()))
+}
+
+/// Encode raw bytes as a prefixed, padded base64url string, such as "encrypted_U...".
+/// This matches cojson's `base64URLencode`.
+pub fn encode_prefixed_base64url(prefix: &str, bytes: &[u8]) -> String {
+    format!("{}{}", prefix, URL_SAFE.encode(bytes))
+}
+
+/// Decode a prefixed, padded base64url string back into raw bytes.
+/// Returns the decoded bytes or CryptoError if the prefix or base64 encoding is invalid.
+pub fn decode_prefixed_base64url(
+    value: &str,
+    prefix: &'static str,
+    field: &'static str,
+) -> Result<Vec<u8>, CryptoError> {
+    let encoded = value
+        .strip_prefix(prefix)
+        .ok_or(CryptoError::InvalidPrefix(prefix, field))?;
+    URL_SAFE
+        .decode(encoded)
+        .map_err(|e| CryptoError::Base64Error(e.to_string()))
+}
+
+/// Internal function to parse a "hash_z" string into its 32 hash bytes.
+pub fn parse_hash_internal(hash: &str) -> Result<[u8; 32], CryptoError> {
+    decode_prefixed_array(hash, HASH_PREFIX, "hash")
+}
+
+/// Internal function to parse a "shortHash_z" string into its truncated hash bytes.
+pub fn parse_short_hash_internal(short_hash: &str) -> Result<[u8; SHORT_HASH_LENGTH], CryptoError> {
+    decode_prefixed_array(short_hash, SHORT_HASH_PREFIX, "short hash")
+}
+
+/// WASM-exposed function to parse a "hash_z" string.
+/// - `hash`: Base58-encoded hash with "hash_z" prefix
+///
+/// Returns the 32 raw hash bytes or throws JsError if the format is invalid.
+#[wasm_bindgen]
+pub fn parse_hash(hash: &str) -> Result<Box<[u8]>, JsError> {
+    Ok(parse_hash_internal(hash)?.into())
+}
+
+/// WASM-exposed function to parse a "shortHash_z" string.
+/// - `short_hash`: Base58-encoded short hash with "shortHash_z" prefix
+///
+/// Returns the raw truncated hash bytes or throws JsError if the format is invalid.
+#[wasm_bindgen]
+pub fn parse_short_hash(short_hash: &str) -> Result<Box<[u8]>, JsError> {
+    Ok(parse_short_hash_internal(short_hash)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefixed_roundtrip() {
+        let bytes = [1u8, 2, 3, 255];
+        let encoded = encode_prefixed("thing_z", &bytes);
+        assert!(encoded.starts_with("thing_z"));
+        assert_eq!(
+            decode_prefixed(&encoded, "thing_z", "thing").unwrap(),
+            bytes.to_vec()
+        );
+
+        // Wrong prefix
+        assert!(matches!(
+            decode_prefixed(&encoded, "other_z", "other"),
+            Err(CryptoError::InvalidPrefix("other_z", "other"))
+        ));
+
+        // Invalid base58
+        assert!(matches!(
+            decode_prefixed("thing_z0OIl", "thing_z", "thing"),
+            Err(CryptoError::Base58Error(_))
+        ));
+
+        // Wrong length
+        assert!(matches!(
+            decode_prefixed_array::<3>(&encoded, "thing_z", "thing"),
+            Err(CryptoError::InvalidLength("thing", 3, 4))
+        ));
+    }
+
+    #[test]
+    fn test_prefixed_base64url_roundtrip() {
+        let bytes = [0xfbu8, 0xff, 0x00, 0x10];
+        let encoded = encode_prefixed_base64url(ENCRYPTED_PREFIX, &bytes);
+        // URL-safe alphabet with padding, like cojson
+        assert_eq!(encoded, "encrypted_U-_8AEA==");
+        assert_eq!(
+            decode_prefixed_base64url(&encoded, ENCRYPTED_PREFIX, "encrypted value").unwrap(),
+            bytes.to_vec()
+        );
+
+        assert!(matches!(
+            decode_prefixed_base64url("binary_U-_8AEA==", ENCRYPTED_PREFIX, "encrypted value"),
+            Err(CryptoError::InvalidPrefix("encrypted_U", "encrypted value"))
+        ));
+        assert!(matches!(
+            decode_prefixed_base64url("encrypted_U+/8AEA==", ENCRYPTED_PREFIX, "encrypted value"),
+            Err(CryptoError::Base64Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_hashes() {
+        let hash = [7u8; 32];
+        let encoded = encode_prefixed(HASH_PREFIX, &hash);
+        assert_eq!(parse_hash_internal(&encoded).unwrap(), hash);
+
+        // A short hash is not a valid hash, and vice versa
+        let short = encode_prefixed(SHORT_HASH_PREFIX, &hash[..SHORT_HASH_LENGTH]);
+        assert!(parse_hash_internal(&short).is_err());
+        assert_eq!(
+            parse_short_hash_internal(&short).unwrap(),
+            hash[..SHORT_HASH_LENGTH]
+        );
+        assert!(parse_short_hash_internal(&encoded).is_err());
+
+        // Truncated hash
+        let truncated = encode_prefixed(HASH_PREFIX, &hash[..31]);
+        assert!(matches!(
+            parse_hash_internal(&truncated),
+            Err(CryptoError::InvalidLength("hash", 32, 31))
+        ));
+    }
+}